@@ -0,0 +1,48 @@
+/*
+ * C ABI of the aoc2020 solvers, built with `cargo build -p aoc2020-ffi` as
+ * target/<profile>/libaoc2020_ffi.so.
+ *
+ * From Python:
+ *
+ *     lib = ctypes.CDLL("target/release/libaoc2020_ffi.so")
+ *     answer = ctypes.c_char_p()
+ *     code = lib.aoc_solve(1, 2, data, len(data), ctypes.byref(answer))
+ *     print(code, answer.value.decode())
+ *     lib.aoc_free_string(answer)
+ */
+
+#ifndef AOC2020_H
+#define AOC2020_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+enum {
+    AOC_OK = 0,
+    AOC_NULL_POINTER = 1,
+    AOC_UNKNOWN_DAY = 2,
+    AOC_UNKNOWN_PART = 3,
+    AOC_INVALID_INPUT = 4,
+    AOC_PANIC = 5,
+};
+
+/*
+ * Solves a part of a day with its default parameters. `*out` receives the answer, or the
+ * error message when the returned code is not AOC_OK, and must be released with
+ * aoc_free_string. `input` may be NULL when `input_len` is zero.
+ */
+int32_t aoc_solve(uint32_t day, uint32_t part, const uint8_t *input, size_t input_len,
+                  char **out);
+
+/* Releases a string returned by aoc_solve, NULL is ignored. */
+void aoc_free_string(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,101 @@
+//! C ABI over the solvers, see `aoc2020.h`.
+//!
+//! Every part runs with the default parameters of its day. Panics are caught and reported
+//! as `AOC_PANIC`, they never unwind into the caller.
+
+use std::ffi::{c_char, CString};
+use std::slice;
+
+use aoc2020::fuzz;
+use aoc2020::params::Params;
+use aoc2020::progress::Progress;
+use aoc2020::solution;
+
+pub const AOC_OK: i32 = 0;
+pub const AOC_NULL_POINTER: i32 = 1;
+pub const AOC_UNKNOWN_DAY: i32 = 2;
+pub const AOC_UNKNOWN_PART: i32 = 3;
+pub const AOC_INVALID_INPUT: i32 = 4;
+pub const AOC_PANIC: i32 = 5;
+
+fn solve(day: u32, part: u32, input: &[u8]) -> Result<String, (i32, String)> {
+    let solution = solution::get(day)
+        .ok_or_else(|| (AOC_UNKNOWN_DAY, format!("no solution for day {}", day)))?;
+    let (_, solver) = solution
+        .parts()
+        .into_iter()
+        .find(|&(p, _)| p == part)
+        .ok_or_else(|| {
+            (
+                AOC_UNKNOWN_PART,
+                format!("day {} has no part {}", day, part),
+            )
+        })?;
+
+    let input = std::str::from_utf8(input)
+        .map_err(|e| (AOC_INVALID_INPUT, format!("input is not UTF-8: {}", e)))?;
+
+    match fuzz::catch(|| (solution.check_input)(input)) {
+        Err(message) => return Err((AOC_PANIC, format!("parser panicked: {}", message))),
+        Ok(Err(errors)) => {
+            let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+            return Err((AOC_INVALID_INPUT, errors.join("\n")));
+        }
+        Ok(Ok(_)) => {}
+    }
+
+    let params = Params::defaults(solution.params);
+    fuzz::catch(|| solver(input, &params, &Progress::none()))
+        .map_err(|message| (AOC_PANIC, format!("part {} panicked: {}", part, message)))
+}
+
+// Messages may quote input lines, which could hold NULs
+fn c_string(s: String) -> CString {
+    CString::new(s.replace('\0', "\\0")).unwrap()
+}
+
+/// Solves a part of a day, storing the answer in `*out`, or the error message when the
+/// returned code is not `AOC_OK`. The string must be released with [`aoc_free_string`].
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes, or be null with `input_len` zero, and
+/// `out` must be a valid pointer to write to.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    part: u32,
+    input: *const u8,
+    input_len: usize,
+    out: *mut *mut c_char,
+) -> i32 {
+    if out.is_null() || (input.is_null() && input_len > 0) {
+        return AOC_NULL_POINTER;
+    }
+
+    let input = if input.is_null() {
+        &[]
+    } else {
+        slice::from_raw_parts(input, input_len)
+    };
+
+    let (code, message) = match solve(day, part, input) {
+        Ok(answer) => (AOC_OK, answer),
+        Err(error) => error,
+    };
+
+    *out = c_string(message).into_raw();
+    code
+}
+
+/// Releases a string returned by [`aoc_solve`], null is ignored.
+///
+/// # Safety
+///
+/// `s` must come from [`aoc_solve`] and not have been released already.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
@@ -0,0 +1,155 @@
+// Loads the built library with dlopen, like a foreign caller would, rather than linking it
+use std::env;
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::fs;
+use std::path::PathBuf;
+use std::ptr;
+
+use aoc2020::vault;
+use aoc2020_ffi::{
+    AOC_INVALID_INPUT, AOC_NULL_POINTER, AOC_OK, AOC_PANIC, AOC_UNKNOWN_DAY, AOC_UNKNOWN_PART,
+};
+
+type Solve = unsafe extern "C" fn(u32, u32, *const u8, usize, *mut *mut c_char) -> i32;
+type FreeString = unsafe extern "C" fn(*mut c_char);
+
+const RTLD_NOW: c_int = 2;
+
+extern "C" {
+    fn dlopen(filename: *const c_char, flags: c_int) -> *mut c_void;
+    fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+    fn dlerror() -> *const c_char;
+}
+
+struct Library {
+    solve: Solve,
+    free_string: FreeString,
+}
+
+// The test binary lives in target/<profile>/deps, next to the library
+fn library_path() -> PathBuf {
+    let deps = env::current_exe().unwrap().parent().unwrap().to_path_buf();
+    let name = format!(
+        "{}aoc2020_ffi{}",
+        env::consts::DLL_PREFIX,
+        env::consts::DLL_SUFFIX
+    );
+
+    [deps.join(&name), deps.parent().unwrap().join(&name)]
+        .into_iter()
+        .find(|path| path.exists())
+        .unwrap_or_else(|| panic!("{} not found near {}", name, deps.display()))
+}
+
+fn load() -> Library {
+    let path = CString::new(library_path().to_str().unwrap()).unwrap();
+
+    unsafe {
+        let handle = dlopen(path.as_ptr(), RTLD_NOW);
+        assert!(
+            !handle.is_null(),
+            "{}",
+            CStr::from_ptr(dlerror()).to_string_lossy()
+        );
+
+        let symbol = |name: &str| {
+            let name = CString::new(name).unwrap();
+            let symbol = dlsym(handle, name.as_ptr());
+            assert!(!symbol.is_null(), "missing symbol {:?}", name);
+            symbol
+        };
+
+        Library {
+            solve: std::mem::transmute::<*mut c_void, Solve>(symbol("aoc_solve")),
+            free_string: std::mem::transmute::<*mut c_void, FreeString>(symbol("aoc_free_string")),
+        }
+    }
+}
+
+impl Library {
+    fn solve(&self, day: u32, part: u32, input: &[u8]) -> (i32, String) {
+        let mut out = ptr::null_mut();
+
+        unsafe {
+            let code = (self.solve)(day, part, input.as_ptr(), input.len(), &mut out);
+            let answer = CStr::from_ptr(out).to_string_lossy().to_string();
+            (self.free_string)(out);
+            (code, answer)
+        }
+    }
+}
+
+fn input(day: u32) -> Vec<u8> {
+    vault::read(format!("../src/day{}/input.txt", day))
+        .unwrap()
+        .into_bytes()
+}
+
+#[test]
+#[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+fn abi_solve() {
+    let lib = load();
+
+    assert_eq!(lib.solve(1, 1, &input(1)), (AOC_OK, "157059".to_string()));
+    assert_eq!(
+        lib.solve(1, 2, &input(1)),
+        (AOC_OK, "165080960".to_string())
+    );
+    assert_eq!(lib.solve(13, 1, &input(13)), (AOC_OK, "2406".to_string()));
+}
+
+#[test]
+fn abi_errors() {
+    let lib = load();
+
+    assert_eq!(
+        lib.solve(26, 1, b""),
+        (AOC_UNKNOWN_DAY, "no solution for day 26".to_string())
+    );
+    assert_eq!(
+        lib.solve(13, 2, b"939\n7,13"),
+        (AOC_UNKNOWN_PART, "day 13 has no part 2".to_string())
+    );
+
+    let (code, message) = lib.solve(1, 1, b"1721\nabc");
+    assert_eq!(code, AOC_INVALID_INPUT);
+    assert!(message.contains("line 2"), "{}", message);
+
+    let (code, message) = lib.solve(1, 1, b"\xff");
+    assert_eq!(code, AOC_INVALID_INPUT);
+    assert!(message.contains("not UTF-8"), "{}", message);
+
+    unsafe {
+        assert_eq!(
+            (lib.solve)(1, 1, ptr::null(), 0, ptr::null_mut()),
+            AOC_NULL_POINTER
+        );
+        assert_eq!(
+            (lib.solve)(1, 1, ptr::null(), 1, &mut ptr::null_mut()),
+            AOC_NULL_POINTER
+        );
+        (lib.free_string)(ptr::null_mut());
+    }
+}
+
+// The codes of the header must match the ones of the library
+#[test]
+fn abi_header() {
+    let header = fs::read_to_string("aoc2020.h").unwrap();
+
+    for (name, code) in [
+        ("AOC_OK", AOC_OK),
+        ("AOC_NULL_POINTER", AOC_NULL_POINTER),
+        ("AOC_UNKNOWN_DAY", AOC_UNKNOWN_DAY),
+        ("AOC_UNKNOWN_PART", AOC_UNKNOWN_PART),
+        ("AOC_INVALID_INPUT", AOC_INVALID_INPUT),
+        ("AOC_PANIC", AOC_PANIC),
+    ] {
+        let line = format!("{} = {},", name, code);
+        assert!(header.contains(&line), "aoc2020.h lacks `{}`", line);
+    }
+
+    for function in ["aoc_solve", "aoc_free_string"] {
+        assert!(header.contains(&format!("{}(", function)));
+    }
+}
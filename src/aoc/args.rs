@@ -0,0 +1,91 @@
+use std::str::FromStr;
+
+use aoc2020::solution::{self, Solution};
+
+// Command line arguments of a subcommand, consumed option by option
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    pub fn new(args: impl Iterator<Item = String>) -> Self {
+        Self {
+            args: args.collect(),
+        }
+    }
+
+    // A leading word, as in `aoc config show`
+    pub fn subcommand(&mut self) -> Option<String> {
+        match self.args.first() {
+            Some(arg) if !arg.starts_with('-') => Some(self.args.remove(0)),
+            _ => None,
+        }
+    }
+
+    pub fn flag(&mut self, name: &str) -> bool {
+        match self.args.iter().position(|arg| arg == name) {
+            Some(idx) => {
+                self.args.remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Accepts both `--name value` and `--name=value`
+    pub fn value(&mut self, name: &str) -> Result<Option<String>, String> {
+        let prefix = format!("{}=", name);
+
+        for idx in 0..self.args.len() {
+            if let Some(value) = self.args[idx].strip_prefix(&prefix) {
+                let value = value.to_string();
+                self.args.remove(idx);
+                return Ok(Some(value));
+            }
+
+            if self.args[idx] == name {
+                if idx + 1 == self.args.len() {
+                    return Err(format!("missing value for {}", name));
+                }
+
+                let value = self.args.remove(idx + 1);
+                self.args.remove(idx);
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    // Every occurrence of a repeatable option
+    pub fn values(&mut self, name: &str) -> Result<Vec<String>, String> {
+        let mut values = vec![];
+        while let Some(value) = self.value(name)? {
+            values.push(value);
+        }
+
+        Ok(values)
+    }
+
+    pub fn parse<T: FromStr>(&mut self, name: &str) -> Result<Option<T>, String> {
+        match self.value(name)? {
+            Some(value) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("invalid value for {}: `{}`", name, value)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn solution(&mut self) -> Result<&'static Solution, String> {
+        let day: u32 = self.parse("--day")?.ok_or("missing --day")?;
+        solution::get(day).ok_or_else(|| format!("no solution for day {}", day))
+    }
+
+    pub fn finish(self) -> Result<(), String> {
+        match self.args.first() {
+            Some(arg) => Err(format!("unexpected argument `{}`", arg)),
+            None => Ok(()),
+        }
+    }
+}
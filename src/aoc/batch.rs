@@ -0,0 +1,267 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use aoc2020::fuzz;
+use aoc2020::json::{Json, ToJson};
+use aoc2020::params::Params;
+use aoc2020::solution::{self, Solution, ANSWERS_EXTENSION};
+use aoc2020::vault;
+
+use crate::args::Args;
+use crate::config;
+use crate::output::Format;
+use crate::run::{self, Budgets};
+
+struct FileResult {
+    name: String,
+    // One per part, or why the file could not be solved
+    answers: Result<Vec<String>, String>,
+    expected: Option<Vec<String>>,
+}
+
+impl FileResult {
+    // Mismatching parts, as (part, expected, actual)
+    fn mismatches(&self) -> Vec<(usize, &str, &str)> {
+        match (&self.answers, &self.expected) {
+            (Ok(answers), Some(expected)) => answers
+                .iter()
+                .zip(expected)
+                .enumerate()
+                .filter(|(_, (answer, expected))| answer != expected)
+                .map(|(idx, (answer, expected))| (idx + 1, expected.as_str(), answer.as_str()))
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    fn is_ok(&self) -> bool {
+        self.answers.is_ok() && self.mismatches().is_empty()
+    }
+
+    fn status(&self) -> String {
+        let mismatches = self.mismatches();
+
+        match (&self.answers, &self.expected) {
+            (Err(error), _) => error.clone(),
+            (Ok(_), None) => "no answers file".to_string(),
+            (Ok(_), Some(_)) if mismatches.is_empty() => "ok".to_string(),
+            (Ok(_), Some(_)) => mismatches
+                .iter()
+                .map(|(part, expected, _)| format!("part {}: expected {}", part, expected))
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+}
+
+impl ToJson for FileResult {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("file", self.name.to_json()),
+            ("answers", self.answers.as_ref().ok().to_json()),
+            ("expected", self.expected.to_json()),
+            ("ok", self.is_ok().to_json()),
+            ("status", self.status().to_json()),
+        ])
+    }
+}
+
+// Every regular file but the answers sidecars, sorted by name. Encrypted inputs are listed
+// under their plain name, which `vault::read` falls back from.
+fn input_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("cannot read {}: {}", dir.display(), e))?;
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path.extension().is_none_or(|ext| ext != ANSWERS_EXTENSION)
+                && !path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        })
+        .map(|path| match path.extension() {
+            Some(ext) if ext == vault::EXTENSION => path.with_extension(""),
+            _ => path,
+        })
+        .collect();
+
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+fn solve_file(
+    solution: &'static Solution,
+    input: &str,
+    budgets: &Budgets,
+) -> Result<Vec<String>, String> {
+    let checked = fuzz::catch(|| (solution.check_input)(input))
+        .map_err(|message| format!("parser panicked: {}", message))?;
+    if let Err(errors) = checked {
+        return Err(format!("invalid input: {} malformed line(s)", errors.len()));
+    }
+
+    let params = Params::defaults(solution.params);
+
+    solution
+        .parts()
+        .into_iter()
+        .map(|(part, _)| {
+            let result = fuzz::catch(|| {
+                run::solve_within(budgets, solution, part, input, &params, false, true)
+            })
+            .map_err(|message| format!("part {} panicked: {}", part, message))?;
+
+            result
+                .map(|result| result.answer)
+                .map_err(|timeout| format!("part {} {}", part, timeout))
+        })
+        .collect()
+}
+
+// A file that cannot be read gets its row like any other failure
+fn solve_dir(
+    solution: &'static Solution,
+    dir: &Path,
+    budgets: &Budgets,
+) -> Result<Vec<FileResult>, String> {
+    let results: Vec<FileResult> = input_files(dir)?
+        .into_iter()
+        .map(|path| FileResult {
+            name: path.file_name().unwrap().to_string_lossy().to_string(),
+            answers: vault::read(&path).and_then(|input| solve_file(solution, &input, budgets)),
+            // `alice.txt` is checked against `alice.answers`
+            expected: solution::read_answers(&path),
+        })
+        .collect();
+
+    if results.is_empty() {
+        return Err(format!("no input file in {}", dir.display()));
+    }
+
+    Ok(results)
+}
+
+pub fn run(mut args: Args) -> Result<ExitCode, String> {
+    let solution = args.solution()?;
+    let dir = PathBuf::from(args.value("--dir")?.ok_or("missing --dir")?);
+    let config = config::load(&mut args, &["format", "budget"])?;
+    let budgets: Budgets = config.parse("budget")?;
+    let format: Format = config.parse("format")?;
+    args.finish()?;
+
+    let results = solve_dir(solution, &dir, &budgets)?;
+
+    match format {
+        Format::Text => {
+            let mut rows = vec![vec!["file".to_string()]];
+            for (part, _) in solution.parts() {
+                rows[0].push(format!("part {}", part));
+            }
+            rows[0].push("check".to_string());
+
+            for result in &results {
+                let mut row = vec![result.name.clone()];
+                match &result.answers {
+                    Ok(answers) => row.extend(answers.iter().cloned()),
+                    Err(_) => row.extend(solution.parts().iter().map(|_| "-".to_string())),
+                }
+                row.push(result.status());
+                rows.push(row);
+            }
+
+            let widths: Vec<usize> = (0..rows[0].len())
+                .map(|col| rows.iter().map(|row| row[col].len()).max().unwrap())
+                .collect();
+
+            for row in rows {
+                let cells: Vec<_> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, &width)| format!("{:width$}", cell, width = width))
+                    .collect();
+                println!("{}", cells.join("  ").trim_end());
+            }
+        }
+        Format::Json => println!("{}", results.to_json().pretty()),
+    }
+
+    if results.iter().all(FileResult::is_ok) {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2020::day1;
+    use std::env;
+
+    const EXAMPLE: &str = "1721\n979\n366\n299\n675\n1456";
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-batch-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn batch_input_files() {
+        let dir = temp_dir("files");
+        for name in [
+            "alice.txt",
+            "alice.answers",
+            ".notes",
+            "bob.txt.enc",
+            "carol.txt",
+            "carol.txt.enc",
+        ] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+        fs::create_dir(dir.join("old")).unwrap();
+
+        let names: Vec<_> = input_files(&dir)
+            .unwrap()
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, ["alice.txt", "bob.txt", "carol.txt"]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn batch_solve_dir() {
+        let dir = temp_dir("solve");
+        fs::write(dir.join("alice.txt"), EXAMPLE).unwrap();
+        fs::write(dir.join("alice.answers"), "514579\n241861950\n").unwrap();
+        fs::write(dir.join("bob.txt"), EXAMPLE).unwrap();
+        fs::write(dir.join("bob.answers"), "514579\n42\n").unwrap();
+        fs::write(dir.join("carol.txt"), EXAMPLE).unwrap();
+        fs::write(dir.join("dave.txt.enc"), "not encrypted").unwrap();
+
+        let results = solve_dir(&day1::SOLUTION, &dir, &Budgets::default()).unwrap();
+        let rows: Vec<_> = results
+            .iter()
+            .map(|result| (result.name.as_str(), result.is_ok(), result.status()))
+            .collect();
+
+        assert_eq!(rows[0], ("alice.txt", true, "ok".to_string()));
+        assert_eq!(
+            rows[1],
+            ("bob.txt", false, "part 2: expected 42".to_string())
+        );
+        assert_eq!(rows[2], ("carol.txt", true, "no answers file".to_string()));
+
+        // The other files are still solved
+        let (name, ok, status) = &rows[3];
+        assert_eq!((*name, *ok), ("dave.txt", false));
+        assert!(status.starts_with("cannot decrypt "), "{}", status);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,165 @@
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc2020::json::{Json, ToJson};
+use aoc2020::memory::{self, MemoryStats};
+use aoc2020::params::Params;
+use aoc2020::solution::{Solution, DEFAULT_VARIANT, SOLUTIONS};
+use aoc2020::vault;
+
+use crate::args::Args;
+use crate::config;
+use crate::output::{self, Format};
+use crate::run;
+
+const DEFAULT_ITERATIONS: usize = 5;
+
+struct Timings {
+    min: Duration,
+    median: Duration,
+    max: Duration,
+    answer: String,
+    memory: Option<MemoryStats>,
+}
+
+fn measure(solution: &Solution, part: u32, input: &str, iterations: usize) -> Timings {
+    let solver = solution.solver(part).unwrap();
+    let params = Params::defaults(solution.params);
+
+    let runs: Vec<_> = (0..iterations)
+        .map(|_| run::solve(solution.day, part, solver, input, &params, false, false))
+        .collect();
+
+    let mut timings: Vec<_> = runs.iter().filter_map(|r| r.elapsed).collect();
+    timings.sort_unstable();
+
+    // Every run allocates the same way, report the first one
+    let first = &runs[0];
+
+    Timings {
+        min: timings[0],
+        median: timings[timings.len() / 2],
+        max: timings[timings.len() - 1],
+        answer: first.answer.clone(),
+        memory: first.memory,
+    }
+}
+
+pub fn run(mut args: Args) -> Result<ExitCode, String> {
+    let solutions: Vec<&Solution> = if args.flag("--all") {
+        SOLUTIONS.to_vec()
+    } else {
+        vec![args.solution()?]
+    };
+
+    let iterations: usize = args
+        .parse("--iterations")?
+        .unwrap_or(DEFAULT_ITERATIONS)
+        .max(1);
+    let compare = args.flag("--compare");
+    let implementation = args.value("--impl")?;
+    if implementation.is_some() && (compare || solutions.len() > 1) {
+        return Err("--impl needs a single --day and no --compare".to_string());
+    }
+    let config = config::load(&mut args, &["input_dir", "format"])?;
+    let format: Format = config.parse("format")?;
+    args.finish()?;
+
+    let mut results = vec![];
+
+    for solution in solutions {
+        let input = vault::read(config.input_path(solution))?;
+
+        for (part, _) in solution.parts() {
+            // Only the variants with their own solver for the part are worth comparing
+            let names = match &implementation {
+                Some(name) => vec![name.as_str()],
+                None if compare => {
+                    let mut names = vec![DEFAULT_VARIANT];
+                    names.extend(
+                        solution
+                            .variants
+                            .iter()
+                            .filter(|variant| variant.solver(part).is_some())
+                            .map(|variant| variant.name),
+                    );
+                    names
+                }
+                None => vec![DEFAULT_VARIANT],
+            };
+            let mut default_median: Option<Duration> = None;
+
+            for &name in &names {
+                let variant = solution.variant(name)?;
+                let timings = measure(&variant, part, &input, iterations);
+
+                match format {
+                    Format::Text => {
+                        let label = if names.len() > 1 || name != DEFAULT_VARIANT {
+                            format!(" [{}]", name)
+                        } else {
+                            String::new()
+                        };
+
+                        // Variants are compared with the default implementation
+                        let ratio = match default_median {
+                            Some(default) if compare => format!(
+                                ", {:.2}x the default",
+                                timings.median.as_secs_f64() / default.as_secs_f64()
+                            ),
+                            _ => String::new(),
+                        };
+
+                        println!(
+                            "Day {} part {}{}: median {:.2?} (min {:.2?}, max {:.2?}, {} runs){}",
+                            solution.day,
+                            part,
+                            label,
+                            timings.median,
+                            timings.min,
+                            timings.max,
+                            iterations,
+                            ratio
+                        );
+
+                        if let Some(memory) = timings.memory {
+                            println!(
+                                "    [memory] peak {} in {} allocations",
+                                memory::format_bytes(memory.peak_bytes),
+                                memory.allocations
+                            );
+                        }
+                    }
+                    Format::Json => {
+                        let mut fields = vec![
+                            ("day", solution.day.to_json()),
+                            ("part", part.to_json()),
+                            ("impl", name.to_json()),
+                            ("answer", timings.answer.to_json()),
+                            ("iterations", iterations.to_json()),
+                            ("min_us", output::micros(timings.min).to_json()),
+                            ("median_us", output::micros(timings.median).to_json()),
+                            ("max_us", output::micros(timings.max).to_json()),
+                        ];
+
+                        if let Some(memory) = timings.memory {
+                            fields.push(("memory", memory.to_json()));
+                        }
+
+                        results.push(Json::object(fields));
+                    }
+                }
+
+                if name == DEFAULT_VARIANT {
+                    default_median = Some(timings.median);
+                }
+            }
+        }
+    }
+
+    if format == Format::Json {
+        println!("{}", Json::Array(results).pretty());
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
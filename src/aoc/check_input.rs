@@ -0,0 +1,50 @@
+use std::process::ExitCode;
+
+use aoc2020::vault;
+
+use crate::args::Args;
+use crate::config;
+
+pub fn run(mut args: Args) -> Result<ExitCode, String> {
+    let solution = args.solution()?;
+    let config = config::load(&mut args, &["input_dir"])?;
+    let path = match args.value("--input")? {
+        Some(path) => path,
+        None => config.input_path(solution).to_string_lossy().to_string(),
+    };
+    let dump = args.flag("--dump");
+    args.finish()?;
+
+    let input = vault::read(&path)?;
+
+    match (solution.check_input)(&input) {
+        Ok(parsed) => {
+            if dump {
+                println!("{}", parsed.pretty());
+            } else {
+                println!(
+                    "day {}: {} is valid ({} lines)",
+                    solution.day,
+                    path,
+                    input.lines().count()
+                );
+            }
+
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(errors) => {
+            println!(
+                "day {}: {} has {} malformed line(s)",
+                solution.day,
+                path,
+                errors.len()
+            );
+
+            for error in errors {
+                println!("  {}", error);
+            }
+
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::process::ExitCode;
+
+use aoc2020::config::{self, Config, KEYS};
+
+use crate::args::Args;
+
+// Reads aoc.toml and the environment, then takes the flags of the settings a command uses
+pub fn load(args: &mut Args, names: &[&str]) -> Result<Config, String> {
+    let content = match fs::read_to_string(config::FILE) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == ErrorKind::NotFound => None,
+        Err(e) => return Err(format!("cannot read {}: {}", config::FILE, e)),
+    };
+
+    let mut flags = vec![];
+    for &name in names {
+        let key = config::key(name).unwrap_or_else(|| panic!("unknown setting `{}`", name));
+        if let Some(value) = args.value(key.flag)? {
+            flags.push((name, value));
+        }
+    }
+
+    Config::load(
+        content.as_deref().map(|content| (config::FILE, content)),
+        |var| env::var(var).ok(),
+        &flags,
+    )
+}
+
+pub fn run(mut args: Args) -> Result<ExitCode, String> {
+    match args.subcommand().as_deref() {
+        Some("show") => {}
+        Some(other) => return Err(format!("unknown config command `{}`", other)),
+        None => return Err("missing config command, expected `show`".to_string()),
+    }
+
+    let names: Vec<_> = KEYS.iter().map(|key| key.name).collect();
+    let config = load(&mut args, &names)?;
+    args.finish()?;
+
+    let width = KEYS.iter().map(|key| key.name.len()).max().unwrap();
+
+    for setting in &config.settings {
+        println!(
+            "{:width$}  {}  ({})",
+            setting.key.name,
+            setting.value,
+            setting.source,
+            width = width
+        );
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
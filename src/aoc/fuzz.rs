@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aoc2020::budget;
+use aoc2020::fuzz;
+use aoc2020::params::Params;
+use aoc2020::progress::Progress;
+use aoc2020::property::Rng;
+use aoc2020::solution::Solution;
+use aoc2020::vault;
+
+use crate::args::Args;
+use crate::config;
+
+const DEFAULT_ITERATIONS: usize = 1000;
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+
+// Runs the parser and every part, returning the first panic
+fn crash(solution: &Solution, input: &str, params: &Params) -> Option<(String, String)> {
+    if let Err(message) = fuzz::catch(|| (solution.check_input)(input)) {
+        return Some(("check_input".to_string(), message));
+    }
+
+    solution.parts().into_iter().find_map(|(part, solver)| {
+        fuzz::catch(|| solver(input, params, &Progress::none()))
+            .err()
+            .map(|message| (format!("part {}", part), message))
+    })
+}
+
+pub fn run(mut args: Args) -> Result<ExitCode, String> {
+    let solution = args.solution()?;
+    let iterations: usize = args.parse("--iterations")?.unwrap_or(DEFAULT_ITERATIONS);
+    let seed: u64 = match args.parse("--seed")? {
+        Some(seed) => seed,
+        // Every run explores new inputs, the seed is printed to replay it
+        None => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64,
+    };
+    let timeout = Duration::from_secs(args.parse("--timeout")?.unwrap_or(DEFAULT_TIMEOUT_SECS));
+    let corpus = PathBuf::from(
+        args.value("--corpus")?
+            .unwrap_or_else(|| fuzz::CORPUS_DIR.to_string()),
+    );
+    let config = config::load(&mut args, &["input_dir"])?;
+    args.finish()?;
+
+    let mut seeds: Vec<String> = solution.examples.iter().map(|s| s.to_string()).collect();
+    if let Ok(input) = vault::read(config.input_path(solution)) {
+        seeds.push(input);
+    }
+
+    println!(
+        "Fuzzing day {} from {} inputs, {} iterations (seed {})",
+        solution.day,
+        seeds.len(),
+        iterations,
+        seed
+    );
+
+    // Puzzle constants making every run slow are lowered
+    let params = Params::fuzzing(solution.params);
+
+    fuzz::silence_panics();
+    let mut rng = Rng::new(seed);
+    // Distinct crashes, keyed by target and panic message, with the number of inputs
+    // hitting each of them and the first one saved
+    let mut crashes: BTreeMap<(String, String), (usize, PathBuf)> = BTreeMap::new();
+    let mut hung = false;
+
+    for _ in 0..iterations {
+        let original = &seeds[rng.below(seeds.len() as u64) as usize];
+        let input = fuzz::mutate(&mut rng, original);
+
+        // A stuck solver stops at its next checkpoint, or is left behind without one
+        let (worker_input, worker_params) = (input.clone(), params.clone());
+        let found = match budget::run(timeout, move || {
+            crash(solution, &worker_input, &worker_params)
+        }) {
+            Ok(found) => found,
+            Err(stuck) => {
+                let path = fuzz::save(&corpus, solution.day, &input)
+                    .map_err(|e| format!("cannot write corpus: {}", e))?;
+                println!("Input {} {}, stopping", path.display(), stuck);
+                hung = true;
+                break;
+            }
+        };
+
+        if let Some(key) = found {
+            let saved = match crashes.get_mut(&key) {
+                Some((count, _)) => {
+                    *count += 1;
+                    continue;
+                }
+                None => fuzz::save(&corpus, solution.day, &input)
+                    .map_err(|e| format!("cannot write corpus: {}", e))?,
+            };
+
+            crashes.insert(key, (1, saved));
+        }
+    }
+
+    if crashes.is_empty() {
+        if hung {
+            return Ok(ExitCode::FAILURE);
+        }
+
+        println!("No crash found");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    println!("{} distinct crash(es):", crashes.len());
+    for ((target, message), (count, path)) in &crashes {
+        println!("  {}: {} ({} inputs)", target, message, count);
+        println!("      first saved as {}", path.display());
+    }
+
+    Ok(ExitCode::FAILURE)
+}
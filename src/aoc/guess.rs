@@ -0,0 +1,94 @@
+use std::path::Path;
+use std::process::ExitCode;
+
+use aoc2020::history::{History, Verdict};
+
+use crate::args::Args;
+use crate::config;
+
+pub fn run(mut args: Args) -> Result<ExitCode, String> {
+    let solution = args.solution()?;
+    let part: u32 = args.parse("--part")?.ok_or("missing --part")?;
+    if solution.solver(part).is_none() {
+        return Err(format!("day {} has no part {}", solution.day, part));
+    }
+
+    let answer = args.value("--answer")?;
+    let verdict: Option<Verdict> = args.parse("--verdict")?;
+    let force = args.flag("--force");
+    let config = config::load(&mut args, &["history_dir"])?;
+    args.finish()?;
+
+    let mut history = History::load(Path::new(config.value("history_dir")), solution.day, part)?;
+
+    let Some(answer) = answer else {
+        if verdict.is_some() {
+            return Err("--verdict needs --answer".to_string());
+        }
+
+        for guess in &history.guesses {
+            println!("{:8}  {}", guess.verdict, guess.answer);
+        }
+
+        match history.bounds() {
+            (None, None) => {}
+            (low, high) => println!(
+                "bounds: above {}, below {}",
+                low.map_or("-".to_string(), |low| low.to_string()),
+                high.map_or("-".to_string(), |high| high.to_string())
+            ),
+        }
+
+        return Ok(ExitCode::SUCCESS);
+    };
+
+    let warnings = history.warnings(&answer);
+
+    let Some(verdict) = verdict else {
+        for warning in &warnings {
+            println!("day {} part {}: {}", solution.day, part, warning);
+        }
+
+        if warnings.is_empty() {
+            println!(
+                "day {} part {}: {} is consistent with {} previous answer(s)",
+                solution.day,
+                part,
+                answer,
+                history.guesses.len()
+            );
+            return Ok(ExitCode::SUCCESS);
+        }
+        return Ok(ExitCode::FAILURE);
+    };
+
+    // The reply of the server is the truth, but a contradiction is more likely a typo
+    let recorded = history.guesses.iter().find(|guess| guess.answer == answer);
+    let conflict = match (recorded, verdict) {
+        (Some(guess), _) => Some(format!(
+            "{} is already recorded as {}",
+            answer, guess.verdict
+        )),
+        (None, Verdict::Correct) if !warnings.is_empty() => Some(warnings.join(", ")),
+        (None, _) => history
+            .accepted()
+            .map(|accepted| format!("the accepted answer is {}", accepted)),
+    };
+
+    if let Some(conflict) = conflict.filter(|_| !force) {
+        return Err(format!(
+            "day {} part {}: {}, use --force to record it anyway",
+            solution.day, part, conflict
+        ));
+    }
+
+    history
+        .record(&answer, verdict)
+        .map_err(|e| format!("cannot write history: {}", e))?;
+    println!(
+        "day {} part {}: recorded {} as {}",
+        solution.day, part, answer, verdict
+    );
+
+    Ok(ExitCode::SUCCESS)
+}
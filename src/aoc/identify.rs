@@ -0,0 +1,68 @@
+use std::process::ExitCode;
+
+use aoc2020::fuzz;
+use aoc2020::identify::{self, Candidate};
+use aoc2020::solution::SOLUTIONS;
+use aoc2020::vault;
+
+use crate::args::Args;
+use crate::config;
+
+fn describe(candidate: &Candidate) -> String {
+    let parse = match &candidate.parse {
+        Ok(0) => "parses".to_string(),
+        Ok(errors) => format!("rejects {} line(s)", errors),
+        Err(message) => format!("parser panics: {}", message),
+    };
+
+    match &candidate.matching {
+        Some(matching) if !matching.is_empty() => {
+            format!("{}, like its input: {}", parse, matching.join(", "))
+        }
+        Some(_) => format!("{}, unlike its input", parse),
+        None => format!("{}, no input to compare with", parse),
+    }
+}
+
+pub fn run(mut args: Args) -> Result<ExitCode, String> {
+    let path = args.subcommand().ok_or("missing input file")?;
+    let all = args.flag("--all");
+    let config = config::load(&mut args, &["input_dir"])?;
+    args.finish()?;
+
+    let input = vault::read(&path)?;
+
+    fuzz::silence_panics();
+    let candidates = identify::rank(&input, &SOLUTIONS, |solution| {
+        vault::read(config.input_path(solution)).ok()
+    });
+
+    let accepted = candidates.iter().filter(|c| c.accepted()).count();
+    match accepted {
+        0 => println!("{}: no day accepts it, closest days:", path),
+        1 => println!("{}: accepted by 1 day", path),
+        n => println!("{}: accepted by {} days", path, n),
+    }
+
+    // Without --all, the closest days stand in for the accepting ones when there are none
+    let shown = if all {
+        candidates.len()
+    } else {
+        accepted.max(3)
+    };
+
+    for candidate in candidates.iter().take(shown) {
+        println!(
+            "  day {:2}  {:3.0}%  {}",
+            candidate.day,
+            candidate.confidence * 100.0,
+            describe(candidate)
+        );
+    }
+
+    Ok(if accepted > 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
@@ -0,0 +1,116 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use aoc2020::solution::{Solution, SOLUTIONS};
+use aoc2020::vault::{self, Key};
+
+use crate::args::Args;
+use crate::config;
+
+// Files of the day's directory with the extension, day8 having two inputs
+fn files(dir: &Path, extension: &str) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("cannot read {}: {}", dir.display(), e))?;
+
+    let mut files = vec![];
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("cannot read {}: {}", dir.display(), e))?
+            .path();
+        if path.extension().is_some_and(|ext| ext == extension) {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+fn encrypt(key: &Key, dir: &Path, remove: bool) -> Result<usize, String> {
+    let inputs = files(dir, "txt")?;
+
+    for path in &inputs {
+        let plaintext =
+            fs::read(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        let encrypted = vault::encrypted_path(path);
+        // Every blob has a fresh salt, so an unchanged input keeps its blob
+        let unchanged = fs::read(&encrypted)
+            .is_ok_and(|blob| vault::decrypt(key, &blob).is_ok_and(|old| old == plaintext));
+        if !unchanged {
+            fs::write(&encrypted, vault::encrypt(key, &plaintext))
+                .map_err(|e| format!("cannot write {}: {}", encrypted.display(), e))?;
+        }
+
+        if remove {
+            fs::remove_file(path)
+                .map_err(|e| format!("cannot remove {}: {}", path.display(), e))?;
+        }
+        println!("{} -> {}", path.display(), encrypted.display());
+    }
+
+    Ok(inputs.len())
+}
+
+fn decrypt(key: &Key, dir: &Path, force: bool) -> Result<usize, String> {
+    let blobs = files(dir, vault::EXTENSION)?;
+
+    for encrypted in &blobs {
+        let path = encrypted.with_extension("");
+        let blob = fs::read(encrypted)
+            .map_err(|e| format!("cannot read {}: {}", encrypted.display(), e))?;
+        let plaintext = vault::decrypt(key, &blob)
+            .map_err(|e| format!("cannot decrypt {}: {}", encrypted.display(), e))?;
+
+        // A plain input edited since it was encrypted is not thrown away
+        if let Ok(existing) = fs::read(&path) {
+            if existing != plaintext && !force {
+                return Err(format!(
+                    "{} differs from {}, use --force to overwrite it",
+                    path.display(),
+                    encrypted.display()
+                ));
+            }
+        }
+
+        fs::write(&path, plaintext)
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+        println!("{} -> {}", encrypted.display(), path.display());
+    }
+
+    Ok(blobs.len())
+}
+
+pub fn run(mut args: Args) -> Result<ExitCode, String> {
+    let command = args.subcommand();
+    let solutions: Vec<&Solution> = if args.flag("--all") {
+        SOLUTIONS.to_vec()
+    } else {
+        vec![args.solution()?]
+    };
+    let remove = args.flag("--remove");
+    let force = args.flag("--force");
+    let config = config::load(&mut args, &["input_dir"])?;
+    args.finish()?;
+
+    let key = Key::from_env().map_err(|e| format!("{}, it holds the passphrase", e))?;
+
+    let mut count = 0;
+    for solution in solutions {
+        let path = config.input_path(solution);
+        let dir = path.parent().unwrap_or(Path::new("."));
+
+        count += match command.as_deref() {
+            Some("encrypt") => encrypt(&key, dir, remove)?,
+            Some("decrypt") if !remove => decrypt(&key, dir, force)?,
+            Some("decrypt") => return Err("--remove only applies to encrypt".to_string()),
+            Some(other) => return Err(format!("unknown inputs command `{}`", other)),
+            None => return Err("missing inputs command, expected encrypt or decrypt".to_string()),
+        };
+    }
+
+    if count == 0 {
+        return Err("no input found".to_string());
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,67 @@
+use std::fs;
+use std::process::ExitCode;
+
+use aoc2020::leaderboard::{self, Leaderboard, Scoring};
+
+use crate::args::Args;
+
+pub fn run(mut args: Args) -> Result<ExitCode, String> {
+    let path = args.value("--file")?.ok_or("missing --file")?;
+    let schemes = match args.parse::<Scoring>("--scoring")? {
+        Some(scoring) => vec![scoring],
+        None => Scoring::ALL.to_vec(),
+    };
+    args.finish()?;
+
+    let content = fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", path, e))?;
+    let board = Leaderboard::parse(&content).map_err(|e| format!("{}: {}", path, e))?;
+
+    println!("Event {}, {} members", board.event, board.members.len());
+
+    for member in &board.members {
+        println!(
+            "\n{}: {} stars, local score {}",
+            member.display_name(),
+            member.stars,
+            member.local_score
+        );
+
+        for day in board.days() {
+            let unlock = board.unlock(day);
+            let star = |part| {
+                member.completions.get(&(day, part)).map(|&ts| {
+                    format!(
+                        "{} (+{})",
+                        leaderboard::format_timestamp(ts),
+                        leaderboard::format_duration(ts - unlock)
+                    )
+                })
+            };
+
+            let Some(part1) = star(1) else {
+                continue;
+            };
+
+            match (star(2), member.delta(day)) {
+                (Some(part2), Some(delta)) => println!(
+                    "    day {:>2}: {} | {} (delta {})",
+                    day,
+                    part1,
+                    part2,
+                    leaderboard::format_duration(delta)
+                ),
+                _ => println!("    day {:>2}: {}", day, part1),
+            }
+        }
+    }
+
+    for scoring in schemes {
+        println!("\nRanking by {} score", scoring);
+
+        for (rank, (member, score)) in board.rank(scoring).into_iter().enumerate() {
+            println!("  {:>3}) {:>5}  {}", rank + 1, score, member.display_name());
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
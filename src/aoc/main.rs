@@ -0,0 +1,128 @@
+mod args;
+mod batch;
+mod bench;
+mod check_input;
+mod config;
+mod fuzz;
+mod guess;
+mod identify;
+mod inputs;
+mod leaderboard;
+mod output;
+mod params;
+mod report;
+mod run;
+
+use std::env;
+use std::process::ExitCode;
+
+use args::Args;
+
+const USAGE: &str = "usage: aoc <command> [options]
+
+commands:
+  run (--day N [--input FILE] | --all) [--format text|json] [--explain] [--stats] [--no-cache]
+      [--budget SECS,DAY=SECS,...] [--jobs N] [--param NAME=VALUE]... [--impl NAME]
+      [--sandbox [--memory-limit MIB] [--cpu-limit SECS]]
+      Solve the puzzles and print the answers.
+      --impl picks another implementation of the day, the error of an unknown one
+      lists them. It skips the cache.
+      --sandbox solves each day in a child process limited to 1024 MiB of memory and
+      120s of CPU by default, reporting a day going over its limits instead of
+      crashing. Linux only.
+      --param overrides a constant of the puzzle statement, see `aoc params`.
+      --budget limits the time of each part, 60s by default, a slow part is reported
+      with the last progress it made.
+      --jobs solves that many days at once with --all, a single one in builds with
+      `--features alloc-stats` unless sandboxed.
+      --explain also prints the intermediate results of the solvers.
+      --stats prints operation counters, needs `--features stats`.
+      --no-cache ignores the answers cached in the cache directory.
+      Answers contradicting the history of `aoc guess` are warned about on stderr.
+  params [--day N]
+      List the puzzle parameters with their defaults.
+  bench (--day N | --all) [--iterations K] [--format text|json] [--impl NAME | --compare]
+      Time each part over several runs.
+      --impl times another implementation of the day, --compare times all of them on
+      the same input, relative to the default one.
+  batch --day N --dir DIR [--budget SECS,DAY=SECS,...] [--format text|json]
+      Solve every input file of a directory, checking the answers against the
+      `<name>.answers` file next to each input, one answer per line.
+  check-input --day N [--input FILE] [--dump]
+      Run only the parser of a day and report every malformed line.
+      --dump prints the parsed structure as JSON.
+  fuzz --day N [--iterations K] [--seed S] [--timeout SECS] [--corpus DIR]
+      Feed mutated examples and inputs to the parser and the solvers, saving the
+      inputs making them panic under fuzz/corpus. Parameters making every run slow,
+      like the turns of day 15 part 2, are lowered.
+  leaderboard --file FILE [--scoring local|stars|delta]
+      Report the stars of a private leaderboard JSON export, ranked under each
+      scoring scheme or only the given one.
+  report [--bench FILE] [--redact] [--readme FILE] [--budget SECS,DAY=SECS,...] [--no-cache]
+      Print a Markdown table of the stars, answers, median timings and sources of every
+      day. Stars are the answers matching the `.answers` file next to each input,
+      timings come from the output of `aoc bench --all --format json` saved to FILE.
+      --redact hides the answers.
+      --readme replaces the part of FILE between `<!-- aoc report -->` and
+      `<!-- /aoc report -->` instead of printing the table.
+  guess --day N --part P [--answer A [--verdict correct|too-high|too-low|wrong] [--force]]
+      Check an answer against the history of the answers given for a part, before
+      giving it to the server. With --verdict, record the server's reply, refused when
+      it contradicts the history unless --force. Without --answer, list the history.
+  identify FILE [--all]
+      Guess which day an input file belongs to. Every day's parser is run on it, and its
+      shape is compared with each day's input: blank-line records, grid width, characters,
+      number range and size. Days are ranked by confidence, those accepting the file first.
+      --all lists every day instead of the accepting ones.
+  inputs (encrypt [--remove] | decrypt [--force]) (--day N | --all)
+      Encrypt the input files of the days as FILE.enc, with the passphrase of AOC_INPUT_KEY,
+      or decrypt them back. Every command, test and binary reading an input falls back on
+      its encrypted copy when the plain file is missing, so only the .enc files need to be
+      committed. Copies already holding the same input are kept as they are. --remove
+      deletes the plain files once encrypted, decrypt refuses to overwrite a plain file
+      differing from its encrypted copy unless --force.
+  config show
+      Print the configuration and where each value comes from.
+
+Settings are read from aoc.toml in the current directory, as `key = value` lines, then
+from the environment and then from the flags of the commands using them:
+  input_dir     AOC_INPUT_DIR     --input-dir     inputs as DIR/dayN/input.txt (src)
+  cache_dir     AOC_CACHE_DIR     --cache-dir     answers cache (.aoc-cache)
+  history_dir   AOC_HISTORY_DIR   --history-dir   answers given and verdicts (.aoc-history)
+  format        AOC_FORMAT        --format        text (default) or json
+  jobs          AOC_JOBS          --jobs          days solved at once, 0 for all cores (1)
+  budget        AOC_BUDGET        --budget        time budget of each part (60)
+
+Builds with `--features alloc-stats` also report peak heap usage and allocations.
+Diagnostics go to stderr, filtered by AOC_LOG=LEVEL,DAY=LEVEL,... with levels off, error,
+warn (default), info, debug and trace, e.g. AOC_LOG=day8=trace.";
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    let command = args.next();
+    let args = Args::new(args);
+
+    let result = match command.as_deref() {
+        Some("check-input") => check_input::run(args),
+        Some("run") => run::run(args),
+        Some("bench") => bench::run(args),
+        Some("batch") => batch::run(args),
+        Some("params") => params::run(args),
+        Some("fuzz") => fuzz::run(args),
+        Some("leaderboard") => leaderboard::run(args),
+        Some("report") => report::run(args),
+        Some("guess") => guess::run(args),
+        Some("identify") => identify::run(args),
+        Some("inputs") => inputs::run(args),
+        Some("config") => config::run(args),
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("aoc: {}", message);
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use std::process::ExitCode;
+
+use aoc2020::solution::{self, Solution, SOLUTIONS};
+
+use crate::args::Args;
+
+pub fn run(mut args: Args) -> Result<ExitCode, String> {
+    let solutions: Vec<&Solution> = match args.parse::<u32>("--day")? {
+        Some(day) => {
+            vec![solution::get(day).ok_or_else(|| format!("no solution for day {}", day))?]
+        }
+        None => SOLUTIONS.to_vec(),
+    };
+    args.finish()?;
+
+    for solution in solutions {
+        for param in solution.params {
+            println!(
+                "day {:>2}  {}={}  ({})",
+                solution.day, param.name, param.default, param.help
+            );
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+use aoc2020::cache::Cache;
+use aoc2020::fuzz;
+use aoc2020::json::Json;
+use aoc2020::params::Params;
+use aoc2020::report::{self, DayReport, PartReport};
+use aoc2020::solution::{self, Solution, SOLUTIONS};
+use aoc2020::vault;
+
+use crate::args::Args;
+use crate::config;
+use crate::run::{self, Budgets};
+
+// Answer of a part from the cache, or solved under the budget
+fn answer(
+    solution: &Solution,
+    part: u32,
+    input: &str,
+    cache: Option<&Cache>,
+    budgets: &Budgets,
+) -> Result<String, String> {
+    if let Some(answer) = cache.and_then(|c| c.get(solution.day, part, solution.version, input)) {
+        return Ok(answer);
+    }
+
+    let params = Params::defaults(solution.params);
+
+    let result =
+        fuzz::catch(|| run::solve_within(budgets, solution, part, input, &params, false, true))
+            .map_err(|message| format!("panicked: {}", message))?
+            .map_err(|timeout| timeout.to_string())?;
+
+    if let Some(cache) = cache {
+        cache
+            .put(solution.day, part, solution.version, input, &result.answer)
+            .map_err(|e| format!("cannot write cache: {}", e))?;
+    }
+
+    Ok(result.answer)
+}
+
+pub fn run(mut args: Args) -> Result<ExitCode, String> {
+    let medians = match args.value("--bench")? {
+        Some(path) => {
+            let content =
+                fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", path, e))?;
+            let bench = Json::parse(&content).map_err(|e| format!("{}: {}", path, e))?;
+            report::medians(&bench).map_err(|e| format!("{}: {}", path, e))?
+        }
+        None => HashMap::new(),
+    };
+    let readme = args.value("--readme")?;
+    let redact = args.flag("--redact");
+    let config = config::load(&mut args, &["input_dir", "cache_dir", "budget"])?;
+    let budgets: Budgets = config.parse("budget")?;
+    let cache = if args.flag("--no-cache") {
+        None
+    } else {
+        Some(Cache::new(config.value("cache_dir")))
+    };
+    args.finish()?;
+
+    let mut days = vec![];
+
+    for solution in SOLUTIONS {
+        let input_path = config.input_path(solution);
+        let input = vault::read(&input_path)?;
+        // The answers of this input, not of the built-in one
+        let expected = solution::read_answers(&input_path).unwrap_or_default();
+
+        let parts = solution
+            .parts()
+            .into_iter()
+            .map(|(part, _)| PartReport {
+                part,
+                answer: answer(solution, part, &input, cache.as_ref(), &budgets),
+                expected: expected.get(part as usize - 1).cloned(),
+                median: medians.get(&(solution.day, part)).copied(),
+            })
+            .collect();
+
+        days.push(DayReport {
+            day: solution.day,
+            source: Path::new(solution.input_path)
+                .with_file_name("mod.rs")
+                .to_string_lossy()
+                .to_string(),
+            parts,
+        });
+    }
+
+    let markdown = report::markdown(&days, redact);
+
+    match readme {
+        Some(path) => {
+            let content =
+                fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", path, e))?;
+            let updated = report::update_section(&content, &markdown)
+                .map_err(|e| format!("{}: {}", path, e))?;
+            fs::write(&path, updated).map_err(|e| format!("cannot write {}: {}", path, e))?;
+            println!("Updated {}", path);
+        }
+        None => print!("{}", markdown),
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
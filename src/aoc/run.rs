@@ -0,0 +1,595 @@
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::path::PathBuf;
+use std::process::{Command, ExitCode, Stdio};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc2020::budget::{self, Timeout};
+use aoc2020::cache::Cache;
+use aoc2020::explain::{self, Note};
+use aoc2020::history::History;
+use aoc2020::json::{Json, ToJson};
+use aoc2020::log;
+use aoc2020::memory::{self, MemoryStats};
+use aoc2020::params::Params;
+use aoc2020::progress::Progress;
+use aoc2020::sandbox::{self, Limits, Violation};
+use aoc2020::solution::{Solution, Solver, SOLUTIONS};
+use aoc2020::stats::{self, Stats};
+use aoc2020::vault;
+
+use crate::args::Args;
+use crate::config;
+use crate::output::{self, Format};
+
+const DEFAULT_BUDGET: Duration = Duration::from_secs(60);
+
+// `SECS` for every day and `DAY=SECS` for a single one, comma separated
+pub struct Budgets {
+    default: Duration,
+    days: HashMap<u32, Duration>,
+}
+
+impl Budgets {
+    pub fn get(&self, day: u32) -> Duration {
+        self.days.get(&day).copied().unwrap_or(self.default)
+    }
+}
+
+impl Default for Budgets {
+    fn default() -> Self {
+        Self {
+            default: DEFAULT_BUDGET,
+            days: HashMap::new(),
+        }
+    }
+}
+
+impl FromStr for Budgets {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let secs = |s: &str| {
+            s.parse::<f64>()
+                .ok()
+                .filter(|secs| *secs > 0.0)
+                .map(Duration::from_secs_f64)
+                .ok_or_else(|| format!("invalid budget `{}`", s))
+        };
+
+        let mut budgets = Budgets::default();
+
+        for item in s.split(',') {
+            match item.split_once('=') {
+                Some((day, value)) => {
+                    let day = day.parse().map_err(|_| format!("invalid day `{}`", day))?;
+                    budgets.days.insert(day, secs(value)?);
+                }
+                None => budgets.default = secs(item)?,
+            }
+        }
+
+        Ok(budgets)
+    }
+}
+
+pub struct PartResult {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    // None when the answer comes from the cache
+    pub elapsed: Option<Duration>,
+    pub notes: Vec<Note>,
+    pub stats: Stats,
+    pub memory: Option<MemoryStats>,
+}
+
+impl PartResult {
+    pub fn cached(day: u32, part: u32, answer: String) -> Self {
+        Self {
+            day,
+            part,
+            answer,
+            elapsed: None,
+            notes: vec![],
+            stats: Stats::default(),
+            memory: None,
+        }
+    }
+
+    fn print_text(&self) {
+        let timing = match self.elapsed {
+            Some(elapsed) => format!("{:.2?}", elapsed),
+            None => "cached".to_string(),
+        };
+
+        println!(
+            "Day {} part {}: {} ({})",
+            self.day, self.part, self.answer, timing
+        );
+
+        for note in &self.notes {
+            println!("    {}: {}", note.key, note.value);
+        }
+
+        for (name, value) in &self.stats.counters {
+            println!("    [stats] {}: {}", name, value);
+        }
+
+        for (name, histogram) in &self.stats.histograms {
+            println!("    [stats] {}: {}", name, histogram);
+        }
+
+        if let Some(memory) = self.memory {
+            println!(
+                "    [memory] peak {} in {} allocations",
+                memory::format_bytes(memory.peak_bytes),
+                memory.allocations
+            );
+        }
+    }
+}
+
+impl ToJson for PartResult {
+    fn to_json(&self) -> Json {
+        let mut fields = vec![
+            ("day", self.day.to_json()),
+            ("part", self.part.to_json()),
+            ("answer", self.answer.to_json()),
+            ("cached", self.elapsed.is_none().to_json()),
+            ("time_us", self.elapsed.map(output::micros).to_json()),
+        ];
+
+        if !self.notes.is_empty() {
+            fields.push(("notes", self.notes.to_json()));
+        }
+
+        if !self.stats.is_empty() {
+            fields.push(("stats", self.stats.to_json()));
+        }
+
+        if let Some(memory) = self.memory {
+            fields.push(("memory", memory.to_json()));
+        }
+
+        Json::object(fields)
+    }
+}
+
+// Runs a solver once, collecting everything the build and the flags allow
+// Progress is only rendered on a terminal, bench leaves it out to keep timings comparable
+pub fn solve(
+    day: u32,
+    part: u32,
+    solver: Solver,
+    input: &str,
+    params: &Params,
+    explain: bool,
+    progress: bool,
+) -> PartResult {
+    let progress = if progress {
+        Progress::terminal(format!("Day {} part {}", day, part))
+    } else {
+        Progress::none()
+    };
+
+    // Timed inside the captures, so that their bookkeeping is left out
+    let timed = || {
+        let start = Instant::now();
+        let answer = solver(input, params, &progress);
+        (answer, start.elapsed())
+    };
+
+    let ((((answer, elapsed), notes), stats), memory) = memory::measure(|| {
+        stats::capture(|| {
+            if explain {
+                explain::capture(timed)
+            } else {
+                (timed(), vec![])
+            }
+        })
+    });
+
+    PartResult {
+        day,
+        part,
+        answer,
+        elapsed: Some(elapsed),
+        notes,
+        stats,
+        memory: if memory::ENABLED { Some(memory) } else { None },
+    }
+}
+
+// Same as `solve`, on a worker thread given up on once the budget is spent
+pub fn solve_within(
+    budgets: &Budgets,
+    solution: &Solution,
+    part: u32,
+    input: &str,
+    params: &Params,
+    explain: bool,
+    progress: bool,
+) -> Result<PartResult, Timeout> {
+    let day = solution.day;
+    let solver = solution
+        .solver(part)
+        .unwrap_or_else(|| panic!("day {} has no part {}", day, part));
+    let input = input.to_string();
+    let params = params.clone();
+    budget::run(budgets.get(day), move || {
+        solve(day, part, solver, &input, &params, explain, progress)
+    })
+}
+
+fn timeout_json(day: u32, part: u32, timeout: &Timeout) -> Json {
+    let checkpoint = timeout.checkpoint.as_ref().map(|checkpoint| {
+        Json::object([
+            ("label", checkpoint.label.to_json()),
+            ("detail", checkpoint.detail.to_json()),
+            ("elapsed_us", output::micros(checkpoint.elapsed).to_json()),
+        ])
+    });
+
+    Json::object([
+        ("day", day.to_json()),
+        ("part", part.to_json()),
+        (
+            "timeout",
+            Json::object([
+                ("budget_us", output::micros(timeout.budget).to_json()),
+                ("checkpoint", checkpoint.to_json()),
+            ]),
+        ),
+    ])
+}
+
+// A part either solved, from the cache or not, or given up on
+type Outcome = Result<PartResult, (u32, u32, Timeout)>;
+
+fn solve_day(
+    solution: &Solution,
+    input: &str,
+    params: &Params,
+    cache: Option<&Cache>,
+    budgets: &Budgets,
+    explain: bool,
+    progress: bool,
+) -> Result<Vec<Outcome>, String> {
+    let mut outcomes = vec![];
+
+    for (part, _) in solution.parts() {
+        let cached = cache.and_then(|c| c.get(solution.day, part, solution.version, input));
+
+        if let Some(answer) = cached {
+            outcomes.push(Ok(PartResult::cached(solution.day, part, answer)));
+            continue;
+        }
+
+        match solve_within(budgets, solution, part, input, params, explain, progress) {
+            Ok(result) => {
+                if let Some(cache) = cache {
+                    cache
+                        .put(solution.day, part, solution.version, input, &result.answer)
+                        .map_err(|e| format!("cannot write cache: {}", e))?;
+                }
+
+                outcomes.push(Ok(result));
+            }
+            Err(timeout) => outcomes.push(Err((solution.day, part, timeout))),
+        }
+    }
+
+    Ok(outcomes)
+}
+
+// Solves the items on `jobs` threads, handing the results over in order as they complete
+fn solve_in_order<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    solve: impl Fn(&T) -> R + Sync,
+    mut handle: impl FnMut(R) -> Result<(), String>,
+) -> Result<(), String> {
+    let next = AtomicUsize::new(0);
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let (sender, next, solve) = (sender.clone(), &next, &solve);
+            scope.spawn(move || loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(idx) else {
+                    break;
+                };
+
+                if sender.send((idx, solve(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        // Results completing early wait for the ones before them
+        let mut pending = BTreeMap::new();
+        let mut expected = 0;
+
+        for (idx, result) in receiver {
+            pending.insert(idx, result);
+
+            while let Some(result) = pending.remove(&expected) {
+                handle(result)?;
+                expected += 1;
+            }
+        }
+
+        Ok(())
+    })
+}
+
+// Exit codes of a child which ran to its end, timeouts included
+const CHILD_EXIT_CODES: [i32; 2] = [0, 1];
+
+struct Child {
+    day: u32,
+    stdout: String,
+    // Some part timed out, or the child was stopped
+    failed: bool,
+    violation: Option<Violation>,
+}
+
+// Runs `aoc run` for the day in a child process under the limits
+fn run_child(solution: &Solution, forwarded: &[String], limits: Limits) -> Result<Child, String> {
+    let exe = env::current_exe().map_err(|e| format!("cannot find the aoc binary: {}", e))?;
+    let mut command = Command::new(exe);
+    command
+        .arg("run")
+        .args(["--day", &solution.day.to_string()])
+        .args(forwarded)
+        .stdin(Stdio::null());
+    limits
+        .apply(&mut command)
+        .map_err(|e| format!("day {}: cannot start the sandbox: {}", solution.day, e))?;
+
+    let output = command
+        .output()
+        .map_err(|e| format!("day {}: cannot start the sandbox: {}", solution.day, e))?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    eprint!("{}", stderr);
+
+    Ok(Child {
+        day: solution.day,
+        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+        failed: !output.status.success(),
+        violation: Violation::classify(&output.status, &stderr, limits, &CHILD_EXIT_CODES),
+    })
+}
+
+pub fn run(mut args: Args) -> Result<ExitCode, String> {
+    let mut solutions: Vec<Solution> = if args.flag("--all") {
+        SOLUTIONS.iter().map(|&solution| *solution).collect()
+    } else {
+        vec![*args.solution()?]
+    };
+
+    let implementation = args.value("--impl")?;
+    if let Some(name) = &implementation {
+        if solutions.len() > 1 {
+            return Err("--impl needs a single --day".to_string());
+        }
+        solutions[0] = solutions[0].variant(name)?;
+    }
+
+    let input_path = args.value("--input")?;
+    if input_path.is_some() && solutions.len() > 1 {
+        return Err("--input needs a single --day".to_string());
+    }
+
+    let overrides = args.values("--param")?;
+    if !overrides.is_empty() && solutions.len() > 1 {
+        return Err("--param needs a single --day".to_string());
+    }
+
+    let sandbox = args.flag("--sandbox");
+    let limits = Limits {
+        memory_mib: args
+            .parse("--memory-limit")?
+            .unwrap_or(sandbox::DEFAULT_MEMORY_MIB),
+        cpu_secs: args
+            .parse("--cpu-limit")?
+            .unwrap_or(sandbox::DEFAULT_CPU_SECS),
+    };
+    if !sandbox && limits != Limits::default() {
+        return Err("--memory-limit and --cpu-limit need --sandbox".to_string());
+    }
+    if sandbox && !sandbox::SUPPORTED {
+        return Err("--sandbox needs Linux".to_string());
+    }
+
+    let config = config::load(
+        &mut args,
+        &[
+            "input_dir",
+            "cache_dir",
+            "history_dir",
+            "format",
+            "jobs",
+            "budget",
+        ],
+    )?;
+    let format: Format = config.parse("format")?;
+    let budgets: Budgets = config.parse("budget")?;
+    let jobs = config.jobs()?;
+    // Allocations are counted for the whole process, the sandboxed days excepted
+    let jobs = if memory::ENABLED && !sandbox { 1 } else { jobs };
+    let explain = args.flag("--explain");
+    let show_stats = args.flag("--stats");
+    if show_stats && !stats::ENABLED {
+        return Err("--stats needs a build with `--features stats`".to_string());
+    }
+
+    // Cached answers come without notes nor stats, and are only valid for the default
+    // parameters, an implementation is picked to run it
+    let cache = if args.flag("--no-cache")
+        || explain
+        || show_stats
+        || !overrides.is_empty()
+        || implementation.is_some()
+    {
+        None
+    } else {
+        Some(Cache::new(config.value("cache_dir")))
+    };
+    args.finish()?;
+
+    let mut days = vec![];
+    for solution in solutions {
+        let path = match &input_path {
+            Some(path) => PathBuf::from(path),
+            None => config.input_path(&solution),
+        };
+        let input = vault::read(&path)?;
+        let params = Params::new(solution.params, &overrides)
+            .map_err(|e| format!("day {}: {}", solution.day, e))?;
+        days.push((solution, input, params));
+    }
+
+    let mut results = vec![];
+    let mut timed_out = false;
+
+    if sandbox {
+        // The child gets the settings resolved here, on its own flags
+        let mut forwarded: Vec<String> = vec![];
+        for name in ["input_dir", "cache_dir", "history_dir", "format", "budget"] {
+            let flag = config.setting(name).key.flag;
+            forwarded.extend([flag.to_string(), config.value(name).to_string()]);
+        }
+        forwarded.extend(["--jobs".to_string(), "1".to_string()]);
+        if let Some(path) = &input_path {
+            forwarded.extend(["--input".to_string(), path.clone()]);
+        }
+        if let Some(name) = &implementation {
+            forwarded.extend(["--impl".to_string(), name.clone()]);
+        }
+        for param in &overrides {
+            forwarded.extend(["--param".to_string(), param.clone()]);
+        }
+        for (flag, set) in [
+            ("--explain", explain),
+            ("--stats", show_stats),
+            ("--no-cache", cache.is_none()),
+        ] {
+            if set {
+                forwarded.push(flag.to_string());
+            }
+        }
+
+        let mut failed = false;
+        solve_in_order(
+            &days,
+            jobs,
+            |(solution, _, _)| run_child(solution, &forwarded, limits),
+            |child| {
+                let child = child?;
+                failed |= child.failed;
+
+                match format {
+                    Format::Text => print!("{}", child.stdout),
+                    // A child stopped midway prints no JSON, its violation stands for its parts
+                    Format::Json => {
+                        if let Ok(Json::Array(items)) = Json::parse(&child.stdout) {
+                            results.extend(items);
+                        }
+                    }
+                }
+
+                match (child.violation, format) {
+                    (None, _) => {}
+                    (Some(violation), Format::Text) => {
+                        println!("Day {}: sandbox: {}", child.day, violation)
+                    }
+                    (Some(violation), Format::Json) => results.push(Json::object([
+                        ("day", child.day.to_json()),
+                        ("sandbox", violation.to_json()),
+                    ])),
+                }
+
+                Ok(())
+            },
+        )?;
+
+        if format == Format::Json {
+            println!("{}", Json::Array(results).pretty());
+        }
+
+        return Ok(if failed {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        });
+    }
+
+    // Answers given for other inputs or parameters say nothing about these ones
+    let history_dir = if input_path.is_none() && overrides.is_empty() {
+        Some(PathBuf::from(config.value("history_dir")))
+    } else {
+        None
+    };
+
+    // Progress lines of concurrent days would overwrite each other
+    let progress = jobs == 1;
+
+    solve_in_order(
+        &days,
+        jobs,
+        |(solution, input, params)| {
+            solve_day(
+                solution,
+                input,
+                params,
+                cache.as_ref(),
+                &budgets,
+                explain,
+                progress,
+            )
+        },
+        |outcomes| {
+            for outcome in outcomes? {
+                if let (Ok(result), Some(dir)) = (&outcome, &history_dir) {
+                    let history = History::load(dir, result.day, result.part)?;
+                    for warning in history.warnings(&result.answer) {
+                        log::warn!("day {} part {}: {}", result.day, result.part, warning);
+                    }
+                }
+
+                match (outcome, format) {
+                    (Ok(result), Format::Text) => result.print_text(),
+                    (Ok(result), Format::Json) => results.push(result.to_json()),
+                    (Err((day, part, timeout)), format) => {
+                        match format {
+                            Format::Text => println!("Day {} part {}: {}", day, part, timeout),
+                            Format::Json => results.push(timeout_json(day, part, &timeout)),
+                        }
+                        timed_out = true;
+                    }
+                }
+            }
+
+            Ok(())
+        },
+    )?;
+
+    if format == Format::Json {
+        println!("{}", Json::Array(results).pretty());
+    }
+
+    if timed_out {
+        Ok(ExitCode::FAILURE)
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
+}
@@ -0,0 +1,168 @@
+//! Time budgets for the solvers.
+//!
+//! [`run`] executes a solver on a worker thread and gives up waiting once its budget is
+//! spent. Long loops call [`checkpoint`], which records how far they got, so a timeout
+//! can be reported with the last checkpoint. Threads cannot be killed: a timed out worker
+//! is cancelled by unwinding from its next checkpoint, or left behind if it never reaches
+//! one.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Checkpoint {
+    pub label: &'static str,
+    pub detail: String,
+    // Since the solver started
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for Checkpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} (at {:.2?})",
+            self.label, self.detail, self.elapsed
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Timeout {
+    pub budget: Duration,
+    pub checkpoint: Option<Checkpoint>,
+}
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "timed out after {:.2?}", self.budget)?;
+
+        match &self.checkpoint {
+            Some(checkpoint) => write!(f, ", last checkpoint {}", checkpoint),
+            None => write!(f, ", no checkpoint reached"),
+        }
+    }
+}
+
+struct Watch {
+    start: Instant,
+    last: Mutex<Option<Checkpoint>>,
+    cancelled: AtomicBool,
+}
+
+// Payload of the unwind stopping a cancelled worker
+struct Cancelled;
+
+thread_local! {
+    static WATCH: RefCell<Option<Arc<Watch>>> = const { RefCell::new(None) };
+}
+
+// The detail is only formatted when the solver runs under a budget
+pub fn checkpoint(label: &'static str, detail: impl FnOnce() -> String) {
+    let watch = WATCH.with(|watch| watch.borrow().clone());
+
+    if let Some(watch) = watch {
+        if watch.cancelled.load(Ordering::Relaxed) {
+            // Not a panic, so no panic hook runs for it
+            panic::resume_unwind(Box::new(Cancelled));
+        }
+
+        *watch.last.lock().unwrap() = Some(Checkpoint {
+            label,
+            detail: detail(),
+            elapsed: watch.start.elapsed(),
+        });
+    }
+}
+
+pub fn run<T, F>(budget: Duration, f: F) -> Result<T, Timeout>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let watch = Arc::new(Watch {
+        start: Instant::now(),
+        last: Mutex::new(None),
+        cancelled: AtomicBool::new(false),
+    });
+
+    let (tx, rx) = mpsc::channel();
+    let worker_watch = watch.clone();
+    let worker = thread::spawn(move || {
+        WATCH.with(|watch| *watch.borrow_mut() = Some(worker_watch));
+        // The receiver is gone when the budget is spent
+        let _ = tx.send(f());
+    });
+
+    match rx.recv_timeout(budget) {
+        Ok(result) => {
+            let _ = worker.join();
+            Ok(result)
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            watch.cancelled.store(true, Ordering::Relaxed);
+
+            Err(Timeout {
+                budget,
+                checkpoint: watch.last.lock().unwrap().clone(),
+            })
+        }
+        // The solver panicked, let it propagate like without a budget
+        Err(mpsc::RecvTimeoutError::Disconnected) => match worker.join() {
+            Err(payload) => panic::resume_unwind(payload),
+            Ok(()) => unreachable!(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_run() {
+        assert_eq!(run(Duration::from_secs(5), || 42), Ok(42));
+
+        // Outside of `run`, checkpoints are ignored
+        checkpoint("ignored", || unreachable!());
+    }
+
+    #[test]
+    fn budget_timeout() {
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        struct Guard(Arc<AtomicBool>);
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::Relaxed);
+            }
+        }
+
+        let guard = Guard(stopped.clone());
+        let timeout = run(Duration::from_millis(50), move || {
+            let _guard = guard;
+            for round in 0.. {
+                checkpoint("loop", || format!("round {}", round));
+                thread::sleep(Duration::from_millis(5));
+            }
+        })
+        .unwrap_err();
+
+        let checkpoint = timeout.checkpoint.unwrap();
+        assert_eq!(checkpoint.label, "loop");
+        assert!(checkpoint.detail.starts_with("round "));
+
+        // The worker unwinds from its next checkpoint
+        let start = Instant::now();
+        while !stopped.load(Ordering::Relaxed) {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+}
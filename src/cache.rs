@@ -0,0 +1,86 @@
+//! On-disk cache of the answers.
+//!
+//! Entries are keyed by a hash of the input and by the version of the solver, so editing
+//! an input or bumping `Solution::version` makes the cached answer stale.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+pub const DEFAULT_DIR: &str = ".aoc-cache";
+
+// FNV-1a, stable across Rust releases unlike the std hashers
+pub fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    // A single entry per day and part, a stale entry is overwritten by the next answer
+    fn path(&self, day: u32, part: u32) -> PathBuf {
+        self.dir.join(format!("day{}-part{}", day, part))
+    }
+
+    fn key(version: u32, input: &str) -> String {
+        format!(
+            "version={}\ninput={:016x}\n",
+            version,
+            hash(input.as_bytes())
+        )
+    }
+
+    pub fn get(&self, day: u32, part: u32, version: u32, input: &str) -> Option<String> {
+        let entry = fs::read_to_string(self.path(day, part)).ok()?;
+        let answer = entry.strip_prefix(&Self::key(version, input))?;
+
+        answer
+            .strip_prefix("answer=")
+            .map(|a| a.trim_end().to_string())
+    }
+
+    pub fn put(
+        &self,
+        day: u32,
+        part: u32,
+        version: u32,
+        input: &str,
+        answer: &str,
+    ) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(
+            self.path(day, part),
+            format!("{}answer={}\n", Self::key(version, input), answer),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn cache_invalidation() {
+        let dir = env::temp_dir().join(format!("aoc-cache-test-{}", std::process::id()));
+        let cache = Cache::new(&dir);
+
+        assert_eq!(cache.get(1, 1, 1, "input"), None);
+
+        cache.put(1, 1, 1, "input", "42").unwrap();
+        assert_eq!(cache.get(1, 1, 1, "input"), Some("42".to_string()));
+        assert_eq!(cache.get(1, 2, 1, "input"), None);
+        assert_eq!(cache.get(1, 1, 2, "input"), None);
+        assert_eq!(cache.get(1, 1, 1, "other input"), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,337 @@
+//! Project configuration of the `aoc` runner.
+//!
+//! Each setting comes from its built-in default, overridden by `aoc.toml` in the current
+//! directory, then by its `AOC_*` environment variable, then by its command line flag.
+//! The file holds flat `key = value` lines, values being quoted strings or bare numbers.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::thread;
+
+use crate::solution::Solution;
+
+pub const FILE: &str = "aoc.toml";
+
+pub struct Key {
+    pub name: &'static str,
+    pub env: &'static str,
+    pub flag: &'static str,
+    pub help: &'static str,
+    default: fn() -> String,
+}
+
+pub static KEYS: [Key; 6] = [
+    Key {
+        name: "input_dir",
+        env: "AOC_INPUT_DIR",
+        flag: "--input-dir",
+        help: "directory holding the dayN/ input files",
+        default: || "src".to_string(),
+    },
+    Key {
+        name: "cache_dir",
+        env: "AOC_CACHE_DIR",
+        flag: "--cache-dir",
+        help: "directory of the answers cache",
+        default: || crate::cache::DEFAULT_DIR.to_string(),
+    },
+    Key {
+        name: "history_dir",
+        env: "AOC_HISTORY_DIR",
+        flag: "--history-dir",
+        help: "directory of the answers given and their verdicts",
+        default: || crate::history::DEFAULT_DIR.to_string(),
+    },
+    Key {
+        name: "format",
+        env: "AOC_FORMAT",
+        flag: "--format",
+        help: "default output format, text or json",
+        default: || "text".to_string(),
+    },
+    Key {
+        name: "jobs",
+        env: "AOC_JOBS",
+        flag: "--jobs",
+        help: "days solved at once by `aoc run --all`, 0 for one per core",
+        default: || "1".to_string(),
+    },
+    Key {
+        name: "budget",
+        env: "AOC_BUDGET",
+        flag: "--budget",
+        help: "time budget of each part, as SECS,DAY=SECS,...",
+        default: || "60".to_string(),
+    },
+];
+
+pub fn key(name: &str) -> Option<&'static Key> {
+    KEYS.iter().find(|key| key.name == name)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    Default,
+    File { path: String, line: usize },
+    Env(&'static str),
+    Flag(&'static str),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::File { path, line } => write!(f, "{}:{}", path, line),
+            Source::Env(var) => write!(f, "env {}", var),
+            Source::Flag(flag) => write!(f, "flag {}", flag),
+        }
+    }
+}
+
+pub struct Setting {
+    pub key: &'static Key,
+    pub value: String,
+    pub source: Source,
+}
+
+pub struct Config {
+    pub settings: Vec<Setting>,
+}
+
+// Lines of the file as (line number, key, value)
+fn parse_file(content: &str) -> Result<Vec<(usize, &str, String)>, String> {
+    let mut entries = vec![];
+
+    for (idx, line) in content.lines().enumerate() {
+        let line_number = idx + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let error = |message: &str| format!("line {}: {}", line_number, message);
+
+        if line.starts_with('[') {
+            return Err(error("tables are not supported"));
+        }
+
+        let (name, value) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected `key = value`"))?;
+        let value = parse_value(value.trim()).map_err(|e| error(&e))?;
+
+        entries.push((line_number, name.trim(), value));
+    }
+
+    Ok(entries)
+}
+
+fn parse_value(s: &str) -> Result<String, String> {
+    let Some(quoted) = s.strip_prefix('"') else {
+        let value = s.split('#').next().unwrap().trim();
+        if value.is_empty() || !value.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            return Err(format!(
+                "expected a quoted string or a number, got `{}`",
+                value
+            ));
+        }
+        return Ok(value.to_string());
+    };
+
+    let mut value = String::new();
+    let mut chars = quoted.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let rest = chars.as_str().trim();
+                if !rest.is_empty() && !rest.starts_with('#') {
+                    return Err(format!("unexpected `{}` after the string", rest));
+                }
+                return Ok(value);
+            }
+            '\\' => match chars.next() {
+                Some('"') => value.push('"'),
+                Some('\\') => value.push('\\'),
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                other => return Err(format!("invalid escape `\\{}`", other.unwrap_or(' '))),
+            },
+            c => value.push(c),
+        }
+    }
+
+    Err("unterminated string".to_string())
+}
+
+impl Config {
+    pub fn defaults() -> Self {
+        Self {
+            settings: KEYS
+                .iter()
+                .map(|key| Setting {
+                    key,
+                    value: (key.default)(),
+                    source: Source::Default,
+                })
+                .collect(),
+        }
+    }
+
+    // `file` is the path and content of the configuration file, `flags` are (key, value)
+    pub fn load(
+        file: Option<(&str, &str)>,
+        env: impl Fn(&str) -> Option<String>,
+        flags: &[(&str, String)],
+    ) -> Result<Self, String> {
+        let mut config = Self::defaults();
+
+        if let Some((path, content)) = file {
+            for (line, name, value) in
+                parse_file(content).map_err(|e| format!("{}: {}", path, e))?
+            {
+                let source = Source::File {
+                    path: path.to_string(),
+                    line,
+                };
+                config
+                    .set(name, value, source)
+                    .map_err(|e| format!("{}:{}: {}", path, line, e))?;
+            }
+        }
+
+        for key in &KEYS {
+            if let Some(value) = env(key.env).filter(|value| !value.is_empty()) {
+                config.set(key.name, value, Source::Env(key.env))?;
+            }
+        }
+
+        for (name, value) in flags {
+            let flag = key(name)
+                .ok_or_else(|| format!("unknown setting `{}`", name))?
+                .flag;
+            config.set(name, value.clone(), Source::Flag(flag))?;
+        }
+
+        Ok(config)
+    }
+
+    fn set(&mut self, name: &str, value: String, source: Source) -> Result<(), String> {
+        let setting = self
+            .settings
+            .iter_mut()
+            .find(|setting| setting.key.name == name)
+            .ok_or_else(|| {
+                let known: Vec<_> = KEYS.iter().map(|key| key.name).collect();
+                format!(
+                    "unknown setting `{}`, expected one of: {}",
+                    name,
+                    known.join(", ")
+                )
+            })?;
+
+        setting.value = value;
+        setting.source = source;
+        Ok(())
+    }
+
+    pub fn setting(&self, name: &str) -> &Setting {
+        self.settings
+            .iter()
+            .find(|setting| setting.key.name == name)
+            .unwrap_or_else(|| panic!("unknown setting `{}`", name))
+    }
+
+    pub fn value(&self, name: &str) -> &str {
+        &self.setting(name).value
+    }
+
+    pub fn parse<T: FromStr>(&self, name: &str) -> Result<T, String> {
+        let setting = self.setting(name);
+        setting.value.parse().map_err(|_| {
+            format!(
+                "invalid {} `{}` (from {})",
+                name, setting.value, setting.source
+            )
+        })
+    }
+
+    // The day's input under the input directory, which stands for `src` in its built-in path
+    pub fn input_path(&self, solution: &Solution) -> PathBuf {
+        let builtin = Path::new(solution.input_path);
+        let relative = builtin.strip_prefix("src").unwrap_or(builtin);
+        Path::new(self.value("input_dir")).join(relative)
+    }
+
+    // `jobs = 0` stands for one job per core
+    pub fn jobs(&self) -> Result<usize, String> {
+        match self.parse("jobs")? {
+            0 => Ok(thread::available_parallelism().map_or(1, |n| n.get())),
+            jobs => Ok(jobs),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day8;
+
+    #[test]
+    fn config_precedence() {
+        let file = "# comment\ncache_dir = \"/tmp/cache\"\nformat = \"json\" # comment\njobs = 4\n";
+        let env = |var: &str| match var {
+            "AOC_FORMAT" => Some("text".to_string()),
+            "AOC_JOBS" => Some("8".to_string()),
+            _ => None,
+        };
+        let flags = [("jobs", "2".to_string())];
+
+        let config = Config::load(Some(("aoc.toml", file)), env, &flags).unwrap();
+        let value = |name| (config.value(name), config.setting(name).source.to_string());
+
+        assert_eq!(value("input_dir"), ("src", "default".to_string()));
+        assert_eq!(value("cache_dir"), ("/tmp/cache", "aoc.toml:2".to_string()));
+        assert_eq!(value("format"), ("text", "env AOC_FORMAT".to_string()));
+        assert_eq!(value("jobs"), ("2", "flag --jobs".to_string()));
+        assert_eq!(config.parse::<usize>("jobs"), Ok(2));
+        assert_eq!(
+            config.parse::<u8>("input_dir"),
+            Err("invalid input_dir `src` (from default)".to_string())
+        );
+
+        assert_eq!(
+            config.input_path(&day8::SOLUTION),
+            PathBuf::from("src/day8/input_ko.txt")
+        );
+        let config = Config::load(None, |_| None, &[("input_dir", "inputs".to_string())]);
+        assert_eq!(
+            config.unwrap().input_path(&day8::SOLUTION),
+            PathBuf::from("inputs/day8/input_ko.txt")
+        );
+    }
+
+    #[test]
+    fn config_file_errors() {
+        let load = |file| Config::load(Some(("aoc.toml", file)), |_| None, &[]).err();
+
+        assert_eq!(
+            load("formats = \"json\""),
+            Some("aoc.toml:1: unknown setting `formats`, expected one of: input_dir, cache_dir, history_dir, format, jobs, budget".to_string())
+        );
+        assert_eq!(
+            load("\n[runner]"),
+            Some("aoc.toml: line 2: tables are not supported".to_string())
+        );
+        assert_eq!(
+            load("format = json"),
+            Some("aoc.toml: line 1: expected a quoted string or a number, got `json`".to_string())
+        );
+        assert_eq!(
+            load("format = \"json"),
+            Some("aoc.toml: line 1: unterminated string".to_string())
+        );
+        assert_eq!(load("budget = \"30,15=120\"\njobs = 0"), None);
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+
+use crate::difftest;
+use crate::explain;
+use crate::input::{self, CheckResult};
+use crate::json::ToJson;
+use crate::params::{self, Param};
+use crate::property::{Property, PropertyTest, Rng};
+use crate::solution::{Solution, Variant};
+
+pub const INPUT_PATH: &str = "src/day1/input.txt";
+pub const TARGET: u32 = 2020;
+
+// From the puzzle statement
+const EXAMPLE: &str = "1721
+979
+366
+299
+675
+1456";
+
+pub fn part1(input: &str, target: u32) -> u32 {
+    let mut numbers: Vec<u32> = input.lines().map(|x| x.parse().unwrap()).collect();
+    numbers.sort_unstable();
+
+    for i in 0..numbers.len() {
+        for j in (i + 1..numbers.len()).rev() {
+            if numbers[i] + numbers[j] == target {
+                explain::note("pair", || vec![numbers[i], numbers[j]].to_json());
+                return numbers[i] * numbers[j];
+            } else if numbers[i] + numbers[j] < target {
+                break;
+            }
+        }
+    }
+
+    0
+}
+
+pub fn part2(input: &str, target: u32) -> u32 {
+    let mut numbers: Vec<u32> = input.lines().map(|x| x.parse().unwrap()).collect();
+    numbers.sort_unstable();
+
+    for i in 0..numbers.len() {
+        for j in i + 1..numbers.len() {
+            for k in j + 1..numbers.len() {
+                let sum = numbers[i] + numbers[j] + numbers[k];
+
+                if sum == target {
+                    explain::note("triple", || {
+                        vec![numbers[i], numbers[j], numbers[k]].to_json()
+                    });
+                    return numbers[i] * numbers[j] * numbers[k];
+                } else if sum > target {
+                    break;
+                }
+            }
+        }
+    }
+
+    0
+}
+
+pub fn part1_hashing(input: &str, target: u32) -> u32 {
+    let mut numbers: Vec<u32> = input.lines().map(|x| x.parse().unwrap()).collect();
+    numbers.sort_unstable();
+
+    let mut counts: HashMap<u32, usize> = HashMap::new();
+    for &x in &numbers {
+        *counts.entry(x).or_default() += 1;
+    }
+
+    // An entry only pairs with itself when it appears twice
+    numbers
+        .iter()
+        .find(|&&x| {
+            x <= target
+                && counts.get(&(target - x)).copied().unwrap_or(0) > usize::from(x * 2 == target)
+        })
+        .map(|&x| x * (target - x))
+        .unwrap_or(0)
+}
+
+pub fn part2_hashing(input: &str, target: u32) -> u32 {
+    let mut numbers: Vec<u32> = input.lines().map(|x| x.parse().unwrap()).collect();
+    numbers.sort_unstable();
+
+    // Last index of each value, so the third number is always taken after the second one
+    let last_idx: HashMap<u32, usize> = numbers.iter().enumerate().map(|(i, &x)| (x, i)).collect();
+
+    for i in 0..numbers.len() {
+        for j in i + 1..numbers.len() {
+            let partial = numbers[i] + numbers[j];
+            if partial > target {
+                break;
+            }
+
+            if let Some(&k) = last_idx.get(&(target - partial)) {
+                if k > j {
+                    return numbers[i] * numbers[j] * numbers[k];
+                }
+            }
+        }
+    }
+
+    0
+}
+
+fn render(numbers: &[u32]) -> String {
+    numbers
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn generate(rng: &mut Rng, terms: usize) -> Vec<u32> {
+    let mut numbers: Vec<u32> = (0..rng.below(20))
+        .map(|_| rng.below(TARGET as u64 + 1) as u32)
+        .collect();
+
+    // Random numbers rarely add up to the target, so plant a solution most of the time
+    if rng.below(4) > 0 {
+        let mut remaining = TARGET;
+        for _ in 1..terms {
+            let x = rng.below(remaining as u64 + 1) as u32;
+            numbers.push(x);
+            remaining -= x;
+        }
+        numbers.push(remaining);
+    }
+
+    // Half the target is only part of a pair when the entry appears twice
+    for _ in 0..rng.below(3) {
+        numbers.push(TARGET / 2);
+    }
+
+    numbers
+}
+
+pub fn difftests() -> Vec<Box<dyn PropertyTest>> {
+    vec![
+        Box::new(Property {
+            name: "day1 part1",
+            generate: |rng| generate(rng, 2),
+            precondition: |_| true,
+            holds: |numbers| {
+                let input = render(numbers);
+                difftest::agree(part1(&input, TARGET), part1_hashing(&input, TARGET))
+            },
+        }),
+        Box::new(Property {
+            name: "day1 part2",
+            generate: |rng| generate(rng, 3),
+            precondition: |_| true,
+            holds: |numbers| {
+                let input = render(numbers);
+                difftest::agree(part2(&input, TARGET), part2_hashing(&input, TARGET))
+            },
+        }),
+    ]
+}
+
+pub fn check_input(input: &str) -> CheckResult {
+    let errors = input::check_lines(input, |line| input::parse::<u32>(line).map(|_| ()));
+
+    input::validated(errors, || {
+        input
+            .lines()
+            .map(|x| x.parse().unwrap())
+            .collect::<Vec<u32>>()
+            .to_json()
+    })
+}
+
+pub const SOLUTION: Solution = Solution {
+    day: 1,
+    input_path: INPUT_PATH,
+    version: 2,
+    examples: &[EXAMPLE],
+    params: &[Param {
+        name: "target",
+        default: "2020",
+        help: "sum of the entries to find",
+        valid: params::parses::<u32>,
+        fuzz: None,
+    }],
+    check_input,
+    part1: |input, params, _| part1(input, params.get("target")).to_string(),
+    part2: Some(|input, params, _| part2(input, params.get("target")).to_string()),
+    variants: &[Variant {
+        name: "hashing",
+        part1: Some(|input, params, _| part1_hashing(input, params.get("target")).to_string()),
+        part2: Some(|input, params, _| part2_hashing(input, params.get("target")).to_string()),
+    }],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault;
+
+    #[test]
+    fn day1_part1() {
+        assert_eq!(part1(EXAMPLE, TARGET), 514579);
+        assert_eq!(part1_hashing(EXAMPLE, TARGET), 514579);
+        assert_eq!(part1("1010\n7", TARGET), 0);
+        assert_eq!(part1_hashing("1010\n7", TARGET), 0);
+        assert_eq!(part1_hashing("1010\n7\n1010", TARGET), 1020100);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day1_part1_input() {
+        assert_eq!(part1(&vault::read(INPUT_PATH).unwrap(), TARGET), 157059);
+    }
+
+    #[test]
+    fn day1_part2() {
+        assert_eq!(part2(EXAMPLE, TARGET), 241861950);
+        assert_eq!(part2_hashing(EXAMPLE, TARGET), 241861950);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day1_part2_input() {
+        assert_eq!(part2(&vault::read(INPUT_PATH).unwrap(), TARGET), 165080960);
+    }
+
+    #[test]
+    fn day1_explain() {
+        let (answer, notes) = explain::capture(|| part1(EXAMPLE, TARGET));
+
+        assert_eq!(answer, 514579);
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].value, vec![299, 1721].to_json());
+    }
+}
@@ -0,0 +1,304 @@
+use crate::difftest;
+use crate::explain;
+use crate::input::{self, CheckResult};
+use crate::json::{Json, ToJson};
+use crate::progress::Progress;
+use crate::property::{Property, PropertyTest, Rng};
+use crate::solution::{Solution, Variant};
+use crate::stats;
+
+pub const INPUT_PATH: &str = "src/day10/input.txt";
+
+// From the puzzle statement
+const EXAMPLE1: &str = "16
+10
+15
+5
+1
+11
+7
+19
+6
+12
+4";
+
+const EXAMPLE2: &str = "28
+33
+18
+42
+31
+14
+46
+20
+48
+47
+24
+23
+49
+45
+19
+38
+39
+11
+1
+32
+25
+35
+8
+17
+7
+9
+4
+2
+34
+10
+3";
+
+fn parse_input(input: &str) -> Vec<u32> {
+    let mut values: Vec<u32> = input.lines().map(|x| x.parse().unwrap()).collect();
+    values.push(0);
+    values.sort_unstable();
+    values.push(values[values.len() - 1] + 3);
+    values
+}
+
+pub fn part1(input: &str) -> u32 {
+    let values = parse_input(input);
+
+    let mut ones = 0;
+    let mut threes = 0;
+
+    for i in 0..(values.len() - 1) {
+        let delta = values[i + 1] - values[i];
+
+        if delta == 1 {
+            ones += 1;
+        } else if delta == 3 {
+            threes += 1;
+        }
+    }
+
+    ones * threes
+}
+
+fn valid_arrangements(values: &mut [Option<u32>]) -> u64 {
+    stats::count!("valid_arrangements calls");
+
+    let values_count = values.len();
+
+    // Assume this function is always called with valid values
+    let mut count = 1;
+
+    for i in 0..(values_count - 1) {
+        // Skip if None
+        if values[i].is_none() {
+            continue;
+        }
+
+        // Get the next valid index
+        let next_idx_offset = values[(i + 1)..values_count]
+            .iter()
+            .enumerate()
+            .find(|(_, &x)| x.is_some());
+
+        // Stop if no more item available
+        let next_idx = {
+            if let Some((offset, _)) = next_idx_offset {
+                i + 1 + offset
+            } else {
+                break;
+            }
+        };
+
+        // No change possible, at this point
+        if values[next_idx].unwrap() - values[i].unwrap() == 3 {
+            continue;
+        }
+
+        // Try to check if the values are still valid with the next element removed.
+        // In this case, try to check the number of valid arrangements in this new branch.
+        if next_idx + 1 < values.len() && values[next_idx + 1].unwrap() - values[i].unwrap() <= 3 {
+            let backup = values[next_idx];
+            values[next_idx] = None;
+
+            count += valid_arrangements(&mut values[i..values_count]);
+
+            values[next_idx] = backup;
+        }
+    }
+
+    count
+}
+
+fn compute_gaps(values: &[u32]) -> Vec<usize> {
+    let mut distances = vec![];
+
+    for i in 0..(values.len() - 1) {
+        distances.push(values[i + 1] - values[i]);
+    }
+
+    distances
+        .iter()
+        .enumerate()
+        .filter_map(
+            |(idx, &distance)| {
+                if distance == 3 {
+                    Some(idx)
+                } else {
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
+pub fn part2(input: &str, progress: &Progress) -> u64 {
+    let values = parse_input(input);
+
+    // Split into subproblems
+    //
+    // A subproblem is a slice that distance of 3 with the previous and the next subproblem.
+    // It means that each subproblem can't have any impact with the other ones, so it can be
+    // resolved independently.
+    //
+    // The final result is the product of all the subproblem results.
+    let mut start_idx = 0;
+    let mut result = 1;
+
+    let gaps = compute_gaps(&values);
+
+    for (idx, &gap) in gaps.iter().enumerate() {
+        // Get problem len
+        let problem_len = (gap - start_idx) + 1;
+
+        // Solve subproblem
+        let mut suproblem: Vec<_> = values
+            .iter()
+            .skip(start_idx)
+            .take(problem_len)
+            .map(|&x| Some(x))
+            .collect();
+
+        stats::record!("subproblem length", problem_len);
+
+        let arrangements = valid_arrangements(&mut suproblem);
+        explain::note("subproblem", || {
+            Json::object([
+                ("values", suproblem.to_json()),
+                ("arrangements", arrangements.to_json()),
+            ])
+        });
+
+        result *= arrangements;
+
+        // Move to next subproblem
+        start_idx += problem_len;
+
+        progress.update(idx as u64 + 1, gaps.len() as u64);
+    }
+
+    result
+}
+
+pub fn part2_dp(input: &str) -> u64 {
+    let values = parse_input(input);
+
+    // Number of arrangements ending with each adapter
+    let mut ways = vec![0; values.len()];
+    ways[0] = 1;
+
+    for i in 1..values.len() {
+        ways[i] = (i.saturating_sub(3)..i)
+            .filter(|&j| values[i] - values[j] <= 3)
+            .map(|j| ways[j])
+            .sum();
+    }
+
+    ways[values.len() - 1]
+}
+
+// Inputs are generated as gaps between adapters, so they always form a valid chain
+fn render(gaps: &[u32]) -> String {
+    gaps.iter()
+        .scan(0, |joltage, gap| {
+            *joltage += gap + 1;
+            Some(joltage.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn generate(rng: &mut Rng) -> Vec<u32> {
+    // The brute force counts arrangements one by one, keep inputs small
+    (0..rng.below(16)).map(|_| rng.below(3) as u32).collect()
+}
+
+pub fn difftests() -> Vec<Box<dyn PropertyTest>> {
+    vec![Box::new(Property {
+        name: "day10 part2",
+        generate,
+        precondition: |_| true,
+        holds: |gaps| {
+            let input = render(gaps);
+            difftest::agree(part2(&input, &Progress::none()), part2_dp(&input))
+        },
+    })]
+}
+
+pub fn check_input(input: &str) -> CheckResult {
+    let errors = input::check_lines(input, |line| input::parse::<u32>(line).map(|_| ()));
+
+    input::validated(errors, || parse_input(input).to_json())
+}
+
+pub const SOLUTION: Solution = Solution {
+    day: 10,
+    input_path: INPUT_PATH,
+    version: 1,
+    examples: &[EXAMPLE1, EXAMPLE2],
+    params: &[],
+    check_input,
+    part1: |input, _, _| part1(input).to_string(),
+    part2: Some(|input, _, progress| part2(input, progress).to_string()),
+    variants: &[Variant {
+        name: "dp",
+        part1: None,
+        part2: Some(|input, _, _| part2_dp(input).to_string()),
+    }],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault;
+
+    #[test]
+    fn day10_part1() {
+        assert_eq!(part1(EXAMPLE1), 7 * 5);
+        assert_eq!(part1(EXAMPLE2), 22 * 10);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day10_part1_input() {
+        assert_eq!(part1(&vault::read(INPUT_PATH).unwrap()), 2812);
+    }
+
+    #[test]
+    fn day10_part2() {
+        assert_eq!(part2(EXAMPLE1, &Progress::none()), 8);
+        assert_eq!(part2(EXAMPLE2, &Progress::none()), 19208);
+        assert_eq!(part2_dp(EXAMPLE1), 8);
+        assert_eq!(part2_dp(EXAMPLE2), 19208);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day10_part2_input() {
+        assert_eq!(
+            part2(&vault::read(INPUT_PATH).unwrap(), &Progress::none()),
+            386869246296064
+        );
+        assert_eq!(part2_dp(&vault::read(INPUT_PATH).unwrap()), 386869246296064);
+    }
+}
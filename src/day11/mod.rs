@@ -0,0 +1,338 @@
+use std::fmt;
+
+use crate::budget;
+use crate::explain;
+use crate::input::{self, CheckResult};
+use crate::json::ToJson;
+use crate::params::{self, Param};
+use crate::progress::Progress;
+use crate::solution::Solution;
+use crate::stats;
+
+pub const INPUT_PATH: &str = "src/day11/input.txt";
+pub const PART1_THRESHOLD: usize = 4;
+pub const PART2_THRESHOLD: usize = 5;
+
+// From the puzzle statement
+const EXAMPLE: &str = "L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+const EMPTY: char = 'L';
+const FLOOR: char = '.';
+const OCCUPIED: char = '#';
+
+#[derive(Clone)]
+struct Seats {
+    seats: Vec<Vec<char>>,
+    columns: i32,
+    rows: i32,
+}
+
+impl Seats {
+    fn new(input: &str) -> Self {
+        let seats: Vec<Vec<char>> = input.lines().map(|x| x.chars().collect()).collect();
+
+        Self {
+            columns: seats[0].len() as i32,
+            rows: seats.len() as i32,
+            seats,
+        }
+    }
+
+    fn is_valid_pos(&self, row: i32, col: i32) -> bool {
+        0 <= row && row < self.rows && 0 <= col && col < self.columns
+    }
+
+    fn occupied_count(&self) -> u32 {
+        self.seats
+            .iter()
+            .flatten()
+            .filter(|&&x| x == OCCUPIED)
+            .count() as u32
+    }
+}
+
+impl fmt::Display for Seats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in &self.seats {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+
+        Ok(())
+    }
+}
+
+type GetOccupiedCount = fn(seats: &Seats, row: i32, column: i32) -> usize;
+
+fn run_round(
+    seats: &Seats,
+    get_occupied_cb: GetOccupiedCount,
+    occupied_thresold: usize,
+) -> (Seats, bool) {
+    let mut new_seats = seats.clone();
+    let mut changes = false;
+
+    for (row_idx, row) in seats.seats.iter().enumerate() {
+        for (column_idx, state) in row.iter().enumerate() {
+            if *state == FLOOR {
+                continue;
+            }
+
+            let new_state = &mut new_seats.seats[row_idx][column_idx];
+            let occupieds = get_occupied_cb(seats, row_idx as i32, column_idx as i32);
+
+            if occupieds == 0 && *state != OCCUPIED {
+                *new_state = OCCUPIED;
+                changes = true;
+            } else if occupieds >= occupied_thresold && *state != EMPTY {
+                *new_state = EMPTY;
+                changes = true;
+            }
+        }
+    }
+
+    (new_seats, changes)
+}
+
+fn run(
+    input: &str,
+    get_occupied_cb: GetOccupiedCount,
+    occupied_thresold: usize,
+    progress: &Progress,
+) -> u32 {
+    let mut seats = Seats::new(input);
+
+    let mut rounds = 0;
+
+    loop {
+        let (new_seats, changes) = run_round(&seats, get_occupied_cb, occupied_thresold);
+        if !changes {
+            break;
+        }
+
+        seats = new_seats;
+        rounds += 1;
+
+        progress.count(rounds);
+        budget::checkpoint("rounds", || {
+            format!("round {}, {} occupied", rounds, seats.occupied_count())
+        });
+
+        stats::count!("rounds");
+        stats::record!("occupied seats per round", seats.occupied_count());
+    }
+
+    explain::note("rounds", || rounds.to_json());
+
+    seats.occupied_count()
+}
+
+fn adjacent_occupied(seats: &Seats, row: i32, column: i32) -> usize {
+    let positions = [
+        (row - 1, column - 1),
+        (row - 1, column),
+        (row - 1, column + 1),
+        (row, column - 1),
+        (row, column + 1),
+        (row + 1, column - 1),
+        (row + 1, column),
+        (row + 1, column + 1),
+    ];
+
+    positions
+        .iter()
+        .filter(|(row, column)| {
+            seats.is_valid_pos(*row, *column)
+                && seats.seats[*row as usize][*column as usize] == OCCUPIED
+        })
+        .count()
+}
+
+fn visible_occupied(seats: &Seats, row: i32, column: i32) -> usize {
+    let mut occupieds = 0;
+
+    let moves = [
+        (-1, 0),  // Left
+        (1, 0),   // Right
+        (0, 1),   // Up
+        (0, -1),  // Down
+        (-1, -1), // Up left
+        (-1, 1),  // Down left
+        (1, -1),  // Up right
+        (1, 1),   // Down lefright
+    ];
+
+    for (move_row, move_column) in moves {
+        let mut next_row = row + move_row;
+        let mut next_column = column + move_column;
+
+        while seats.is_valid_pos(next_row, next_column) {
+            let state = seats.seats[next_row as usize][next_column as usize];
+
+            if state == EMPTY {
+                break;
+            } else if state == OCCUPIED {
+                occupieds += 1;
+                break;
+            } else {
+                next_row += move_row;
+                next_column += move_column;
+            }
+        }
+    }
+
+    occupieds
+}
+
+pub fn part1(input: &str, threshold: usize, progress: &Progress) -> u32 {
+    run(input, adjacent_occupied, threshold, progress)
+}
+
+pub fn part2(input: &str, threshold: usize, progress: &Progress) -> u32 {
+    run(input, visible_occupied, threshold, progress)
+}
+
+pub fn check_input(input: &str) -> CheckResult {
+    let mut width = None;
+
+    let errors = input::check_lines(input, |line| {
+        if let Some(c) = line
+            .chars()
+            .find(|&c| c != EMPTY && c != FLOOR && c != OCCUPIED)
+        {
+            return Err(format!("unexpected character `{}`", c));
+        }
+
+        match width {
+            _ if line.is_empty() => Err("empty row".to_string()),
+            None => {
+                width = Some(line.len());
+                Ok(())
+            }
+            Some(width) if width != line.len() => {
+                Err(format!("expected {} columns, got {}", width, line.len()))
+            }
+            Some(_) => Ok(()),
+        }
+    });
+
+    input::validated(errors, || {
+        Seats::new(input)
+            .seats
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .to_json()
+    })
+}
+
+pub const SOLUTION: Solution = Solution {
+    day: 11,
+    input_path: INPUT_PATH,
+    version: 1,
+    examples: &[EXAMPLE],
+    params: &[
+        Param {
+            name: "part1_threshold",
+            default: "4",
+            help: "occupied adjacent seats making a seat empty in part 1",
+            valid: params::parses::<usize>,
+            fuzz: None,
+        },
+        Param {
+            name: "part2_threshold",
+            default: "5",
+            help: "occupied visible seats making a seat empty in part 2",
+            valid: params::parses::<usize>,
+            fuzz: None,
+        },
+    ],
+    check_input,
+    part1: |input, params, progress| {
+        part1(input, params.get("part1_threshold"), progress).to_string()
+    },
+    part2: Some(|input, params, progress| {
+        part2(input, params.get("part2_threshold"), progress).to_string()
+    }),
+    variants: &[],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot;
+    use crate::vault;
+
+    fn render_rounds(get_occupied_cb: GetOccupiedCount, threshold: usize) -> String {
+        let mut seats = Seats::new(EXAMPLE);
+        let mut rounds = vec![seats.to_string()];
+
+        loop {
+            let (new_seats, changes) = run_round(&seats, get_occupied_cb, threshold);
+            if !changes {
+                break;
+            }
+
+            seats = new_seats;
+            rounds.push(seats.to_string());
+        }
+
+        rounds.join("\n")
+    }
+
+    #[test]
+    fn day11_part1() {
+        assert_eq!(part1(EXAMPLE, PART1_THRESHOLD, &Progress::none()), 37);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "slow-tests"), ignore = "slow in debug builds")]
+    fn day11_part1_input() {
+        assert_eq!(
+            part1(
+                &vault::read(INPUT_PATH).unwrap(),
+                PART1_THRESHOLD,
+                &Progress::none()
+            ),
+            2183
+        );
+    }
+
+    #[test]
+    fn day11_part2() {
+        assert_eq!(part2(EXAMPLE, PART2_THRESHOLD, &Progress::none()), 26);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "slow-tests"), ignore = "slow in debug builds")]
+    fn day11_part2_input() {
+        assert_eq!(
+            part2(
+                &vault::read(INPUT_PATH).unwrap(),
+                PART2_THRESHOLD,
+                &Progress::none()
+            ),
+            1990
+        );
+    }
+
+    #[test]
+    fn day11_rounds() {
+        snapshot::assert_snapshot!(
+            "part1_rounds",
+            render_rounds(adjacent_occupied, PART1_THRESHOLD)
+        );
+        snapshot::assert_snapshot!(
+            "part2_rounds",
+            render_rounds(visible_occupied, PART2_THRESHOLD)
+        );
+    }
+}
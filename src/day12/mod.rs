@@ -0,0 +1,189 @@
+use crate::input::{self, CheckResult};
+use crate::json::{Json, ToJson};
+use crate::solution::Solution;
+
+pub const INPUT_PATH: &str = "src/day12/input.txt";
+
+// From the puzzle statement
+const EXAMPLE: &str = "F10
+N3
+F7
+R90
+F11";
+
+fn move_direction(x: i32, y: i32, direction: char, delta: i32) -> (i32, i32) {
+    match direction {
+        'N' => (x, y + delta),
+        'S' => (x, y - delta),
+        'E' => (x + delta, y),
+        'W' => (x - delta, y),
+        _ => {
+            panic!("Unexpected action {}", direction);
+        }
+    }
+}
+
+fn rotate(x: i32, y: i32, angle: i32) -> (i32, i32) {
+    match angle {
+        90 => (-y, x),
+        180 => (-x, -y),
+        270 => (y, -x),
+        _ => {
+            panic!("Unexpected angle ")
+        }
+    }
+}
+
+fn distance(x: i32, y: i32) -> i32 {
+    x.abs() + y.abs()
+}
+
+pub fn part1(input: &str) -> i32 {
+    let instructions: Vec<(char, i32)> = input
+        .lines()
+        .map(|l| (l.chars().next().unwrap(), l[1..l.len()].parse().unwrap()))
+        .collect();
+
+    let mut waypoint_x: i32 = 1;
+    let mut waypoint_y: i32 = 0;
+
+    let mut x: i32 = 0;
+    let mut y: i32 = 0;
+
+    for (action, value) in instructions {
+        match action {
+            'N' | 'S' | 'E' | 'W' => {
+                let delta = move_direction(x, y, action, value);
+                x = delta.0;
+                y = delta.1;
+            }
+            'L' => {
+                let delta = rotate(waypoint_x, waypoint_y, value);
+                waypoint_x = delta.0;
+                waypoint_y = delta.1;
+            }
+            'R' => {
+                let delta = rotate(waypoint_x, waypoint_y, 360 - value);
+                waypoint_x = delta.0;
+                waypoint_y = delta.1;
+            }
+            'F' => {
+                x += waypoint_x * value;
+                y += waypoint_y * value;
+            }
+            _ => {
+                panic!("Unexpected action {}", action);
+            }
+        }
+    }
+
+    distance(x, y)
+}
+
+pub fn part2(input: &str) -> i32 {
+    let instructions: Vec<(char, i32)> = input
+        .lines()
+        .map(|l| (l.chars().next().unwrap(), l[1..l.len()].parse().unwrap()))
+        .collect();
+
+    let mut waypoint_x: i32 = 10;
+    let mut waypoint_y: i32 = 1;
+
+    let mut x: i32 = 0;
+    let mut y: i32 = 0;
+
+    for (action, value) in instructions {
+        match action {
+            'N' | 'S' | 'E' | 'W' => {
+                let delta = move_direction(waypoint_x, waypoint_y, action, value);
+                waypoint_x = delta.0;
+                waypoint_y = delta.1;
+            }
+            'L' => {
+                let delta = rotate(waypoint_x, waypoint_y, value);
+                waypoint_x = delta.0;
+                waypoint_y = delta.1;
+            }
+            'R' => {
+                let delta = rotate(waypoint_x, waypoint_y, 360 - value);
+                waypoint_x = delta.0;
+                waypoint_y = delta.1;
+            }
+            'F' => {
+                x += waypoint_x * value;
+                y += waypoint_y * value;
+            }
+            _ => {
+                panic!("Unexpected action {}", action);
+            }
+        }
+    }
+
+    distance(x, y)
+}
+
+pub fn check_input(input: &str) -> CheckResult {
+    let mut instructions = vec![];
+
+    let errors = input::check_lines(input, |line| {
+        let mut chars = line.chars();
+        let action = chars.next().ok_or("empty line")?;
+        let value: i32 = input::parse(chars.as_str())?;
+
+        match action {
+            'N' | 'S' | 'E' | 'W' | 'F' => {}
+            'L' | 'R' if [90, 180, 270].contains(&value) => {}
+            'L' | 'R' => return Err(format!("unsupported angle {}", value)),
+            _ => return Err(format!("unknown action `{}`", action)),
+        }
+
+        instructions.push(Json::object([
+            ("action", action.to_json()),
+            ("value", value.to_json()),
+        ]));
+
+        Ok(())
+    });
+
+    input::validated(errors, || Json::Array(instructions))
+}
+
+pub const SOLUTION: Solution = Solution {
+    day: 12,
+    input_path: INPUT_PATH,
+    version: 1,
+    examples: &[EXAMPLE],
+    params: &[],
+    check_input,
+    part1: |input, _, _| part1(input).to_string(),
+    part2: Some(|input, _, _| part2(input).to_string()),
+    variants: &[],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault;
+
+    #[test]
+    fn day12_part1() {
+        assert_eq!(part1(EXAMPLE), 25);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day12_part1_input() {
+        assert_eq!(part1(&vault::read(INPUT_PATH).unwrap()), 362);
+    }
+
+    #[test]
+    fn day12_part2() {
+        assert_eq!(part2(EXAMPLE), 286);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day12_part2_input() {
+        assert_eq!(part2(&vault::read(INPUT_PATH).unwrap()), 29895);
+    }
+}
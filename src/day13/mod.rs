@@ -0,0 +1,109 @@
+use crate::explain;
+use crate::input::{self, CheckResult, InputError};
+use crate::json::{Json, ToJson};
+use crate::solution::Solution;
+
+pub const INPUT_PATH: &str = "src/day13/input.txt";
+
+// From the puzzle statement
+const EXAMPLE: &str = "939
+7,13,x,x,59,x,31,19";
+
+pub fn part1(input: &str) -> u32 {
+    let lines: Vec<_> = input.lines().collect();
+
+    let ts: u32 = lines[0].parse().unwrap();
+    let bus_ids: Vec<u32> = lines[1]
+        .split(',')
+        .filter_map(|x| {
+            if x != "x" {
+                Some(x.parse().unwrap())
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let (id, wait) = bus_ids
+        .iter()
+        .map(|id| (id, id - (ts % id)))
+        .min_by(|(_, x), (_, y)| x.cmp(y))
+        .unwrap();
+
+    explain::note("bus", || {
+        Json::object([("id", id.to_json()), ("wait", wait.to_json())])
+    });
+
+    id * wait
+}
+
+pub fn check_input(input: &str) -> CheckResult {
+    let mut line_idx = 0;
+    let mut timestamp = None;
+    let mut bus_ids = vec![];
+
+    let mut errors = input::check_lines(input, |line| {
+        line_idx += 1;
+
+        match line_idx {
+            1 => {
+                timestamp = Some(input::parse::<u32>(line)?);
+            }
+            2 => {
+                for id in line.split(',') {
+                    if id == "x" {
+                        bus_ids.push(Json::Null);
+                    } else {
+                        match input::parse::<u32>(id)? {
+                            0 => return Err("bus ids must be positive".to_string()),
+                            id => bus_ids.push(id.to_json()),
+                        }
+                    }
+                }
+            }
+            _ => return Err("expected only a timestamp and a bus list".to_string()),
+        }
+
+        Ok(())
+    });
+
+    if line_idx == 1 {
+        errors.push(InputError::new(2, "", "missing bus list"));
+    }
+
+    input::validated(errors, || {
+        Json::object([
+            ("timestamp", timestamp.to_json()),
+            ("bus_ids", Json::Array(bus_ids)),
+        ])
+    })
+}
+
+pub const SOLUTION: Solution = Solution {
+    day: 13,
+    input_path: INPUT_PATH,
+    version: 1,
+    examples: &[EXAMPLE],
+    params: &[],
+    check_input,
+    part1: |input, _, _| part1(input).to_string(),
+    part2: None,
+    variants: &[],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault;
+
+    #[test]
+    fn day13_part1() {
+        assert_eq!(part1(EXAMPLE), 295);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day13_part1_input() {
+        assert_eq!(part1(&vault::read(INPUT_PATH).unwrap()), 2406);
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use crate::input::{self, CheckResult};
+use crate::json::{Json, ToJson};
+use crate::solution::Solution;
+
+pub const INPUT_PATH: &str = "src/day14/input.txt";
+
+// From the puzzle statement
+const EXAMPLE1: &str = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0";
+
+const EXAMPLE2: &str = "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1";
+
+pub fn part1(input: &str) -> u64 {
+    let mut mask: Option<&str> = None;
+    let mut map: HashMap<u64, u64> = HashMap::new();
+
+    let mut sum: u64 = 0;
+
+    for l in input.lines() {
+        if l.starts_with("mask") {
+            mask = Some(&l[7..l.len()]);
+        } else {
+            let mask = mask.unwrap();
+
+            let closing_bracket = l.find(']').unwrap();
+            let addr: u64 = l[4..closing_bracket].parse().unwrap();
+            let mut value: u64 = l[closing_bracket + 4..l.len()].parse().unwrap();
+
+            for (idx, b) in mask.chars().enumerate().filter(|(_, x)| *x != 'X') {
+                let idx = mask.len() - idx - 1;
+
+                if b == '1' {
+                    value |= 1 << idx;
+                } else {
+                    value &= !(1 << idx);
+                }
+
+                let entry = map.entry(addr).or_insert(0);
+                *entry = value;
+            }
+        }
+    }
+
+    sum += map.values().sum::<u64>();
+
+    sum
+}
+
+pub fn part2(input: &str) -> u64 {
+    let mut mask: Option<&str> = None;
+    let mut map: HashMap<u64, u64> = HashMap::new();
+
+    let mut sum: u64 = 0;
+
+    for l in input.lines() {
+        if l.starts_with("mask") {
+            mask = Some(&l[7..l.len()]);
+        } else {
+            // Parse
+            let mask = mask.unwrap();
+            let closing_bracket = l.find(']').unwrap();
+            let value: u64 = l[closing_bracket + 4..l.len()].parse().unwrap();
+            let mut addr: u64 = l[4..closing_bracket].parse().unwrap();
+
+            // Set 1 bits
+            for (idx, b) in mask.chars().enumerate().filter(|(_, x)| *x != 'X') {
+                let idx = mask.len() - idx - 1;
+
+                if b == '1' {
+                    addr |= 1 << idx;
+                }
+            }
+
+            // Generate addresses
+            let mut bit_map = vec![];
+            for (idx, _) in mask.chars().enumerate().filter(|(_, x)| *x == 'X') {
+                bit_map.push(mask.len() - idx - 1);
+            }
+
+            for i in 0..(1 << bit_map.len()) {
+                for bit_idx in 0..bit_map.len() {
+                    addr = if i & (1 << (bit_map.len() - bit_idx - 1)) != 0 {
+                        addr | (1 << bit_map[bit_idx])
+                    } else {
+                        addr & !(1 << bit_map[bit_idx])
+                    };
+                }
+
+                let entry = map.entry(addr).or_insert(0);
+                *entry = value;
+            }
+        }
+    }
+
+    sum += map.values().sum::<u64>();
+
+    sum
+}
+
+pub fn check_input(input: &str) -> CheckResult {
+    let mut instructions = vec![];
+    let mut has_mask = false;
+
+    let errors = input::check_lines(input, |line| {
+        if let Some(mask) = line.strip_prefix("mask = ") {
+            if mask.len() != 36 || !mask.chars().all(|c| c == '0' || c == '1' || c == 'X') {
+                return Err("expected a 36 bits mask made of `0`, `1` and `X`".to_string());
+            }
+
+            has_mask = true;
+            instructions.push(Json::object([("mask", mask.to_json())]));
+        } else if let Some(write) = line.strip_prefix("mem[") {
+            let (addr, value) = write
+                .split_once("] = ")
+                .ok_or("expected `mem[<address>] = <value>`")?;
+            let (addr, value): (u64, u64) = (input::parse(addr)?, input::parse(value)?);
+
+            if !has_mask {
+                return Err("memory write before any mask".to_string());
+            }
+
+            instructions.push(Json::object([
+                ("addr", addr.to_json()),
+                ("value", value.to_json()),
+            ]));
+        } else {
+            return Err("expected `mask = <mask>` or `mem[<address>] = <value>`".to_string());
+        }
+
+        Ok(())
+    });
+
+    input::validated(errors, || Json::Array(instructions))
+}
+
+pub const SOLUTION: Solution = Solution {
+    day: 14,
+    input_path: INPUT_PATH,
+    version: 1,
+    examples: &[EXAMPLE1, EXAMPLE2],
+    params: &[],
+    check_input,
+    part1: |input, _, _| part1(input).to_string(),
+    part2: Some(|input, _, _| part2(input).to_string()),
+    variants: &[],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault;
+
+    #[test]
+    fn day14_part1() {
+        assert_eq!(part1(EXAMPLE1), 165);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day14_part1_input() {
+        assert_eq!(part1(&vault::read(INPUT_PATH).unwrap()), 7817357407588);
+    }
+
+    #[test]
+    fn day14_part2() {
+        assert_eq!(part2(EXAMPLE2), 208);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "slow-tests"), ignore = "slow in debug builds")]
+    fn day14_part2_input() {
+        assert_eq!(part2(&vault::read(INPUT_PATH).unwrap()), 4335927555692);
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use crate::budget;
+use crate::difftest;
+use crate::input::{self, CheckResult};
+use crate::json::ToJson;
+use crate::params::Param;
+use crate::progress::Progress;
+use crate::property::{Property, PropertyTest, Rng};
+use crate::solution::{Solution, Variant};
+use crate::stats;
+
+pub const INPUT_PATH: &str = "src/day15/input.txt";
+pub const PART1_TURNS: u32 = 2020;
+pub const PART2_TURNS: u32 = 30000000;
+
+// From the puzzle statement
+const EXAMPLES: [&str; 7] = [
+    "0,3,6", "1,3,2", "2,1,3", "1,2,3", "2,3,1", "3,2,1", "3,1,2",
+];
+
+pub fn solve(input: &str, turns: u32, progress: &Progress) -> u32 {
+    let starting: Vec<u32> = input.split(',').map(|v| v.parse().unwrap()).collect();
+    // The first turns speak the starting numbers, turn 0 is taken as the first one
+    if turns as usize <= starting.len() {
+        return starting[turns.max(1) as usize - 1];
+    }
+
+    let mut values: HashMap<u32, u32> = HashMap::new();
+    let mut last_spoken: u32 = 0;
+    let mut turn = 0;
+
+    for v in starting {
+        values.insert(v, turn);
+
+        last_spoken = v;
+        turn += 1;
+    }
+
+    values.remove(&last_spoken);
+
+    while turn < turns {
+        stats::count!("map lookups");
+
+        if let Some(entry) = values.get_mut(&last_spoken) {
+            last_spoken = (turn - 1) - *entry;
+            *entry = turn - 1;
+        } else {
+            stats::count!("map inserts");
+            values.insert(last_spoken, turn - 1);
+            last_spoken = 0;
+        }
+
+        turn += 1;
+
+        if turn % (1 << 16) == 0 {
+            progress.update(turn as u64, turns as u64);
+            budget::checkpoint("turns", || format!("turn {} of {}", turn, turns));
+        }
+    }
+
+    last_spoken
+}
+
+// Same as `solve`, with the turns indexed by number in a Vec instead of a HashMap
+pub fn solve_vec(input: &str, turns: u32, progress: &Progress) -> u32 {
+    let starting: Vec<u32> = input.split(',').map(|v| v.parse().unwrap()).collect();
+    // The first turns speak the starting numbers, turn 0 is taken as the first one
+    if turns as usize <= starting.len() {
+        return starting[turns.max(1) as usize - 1];
+    }
+
+    // Spoken numbers are ages, always below the number of turns, larger starting numbers
+    // would size the table after them instead
+    if starting.iter().any(|&v| v >= turns) {
+        return solve(input, turns, progress);
+    }
+    // Turn after the one each number was last spoken at, 0 when never spoken
+    let mut next_turns = vec![0u32; turns as usize];
+
+    for (turn, &v) in starting.iter().enumerate() {
+        next_turns[v as usize] = turn as u32 + 1;
+    }
+
+    let mut last_spoken = *starting.last().unwrap();
+    next_turns[last_spoken as usize] = 0;
+
+    for turn in starting.len() as u32..turns {
+        let next_turn = std::mem::replace(&mut next_turns[last_spoken as usize], turn);
+        last_spoken = if next_turn == 0 { 0 } else { turn - next_turn };
+
+        if (turn + 1) % (1 << 16) == 0 {
+            progress.update(turn as u64 + 1, turns as u64);
+            budget::checkpoint("turns", || format!("turn {} of {}", turn + 1, turns));
+        }
+    }
+
+    last_spoken
+}
+
+fn render(numbers: &[u32]) -> String {
+    numbers
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn generate(rng: &mut Rng) -> (Vec<u32>, u32) {
+    let numbers = (0..1 + rng.below(6))
+        .map(|_| match rng.below(10) {
+            0 => u32::MAX - rng.below(3) as u32,
+            _ => rng.below(20) as u32,
+        })
+        .collect();
+    // Sometimes fewer turns than starting numbers
+    let turns = if rng.below(4) == 0 {
+        rng.below(8)
+    } else {
+        rng.below(3000)
+    };
+    (numbers, turns as u32)
+}
+
+pub fn difftests() -> Vec<Box<dyn PropertyTest>> {
+    vec![Box::new(Property {
+        name: "day15 vec",
+        generate,
+        precondition: |(numbers, _)| !numbers.is_empty(),
+        holds: |(numbers, turns)| {
+            let input = render(numbers);
+            difftest::agree(
+                solve(&input, *turns, &Progress::none()),
+                solve_vec(&input, *turns, &Progress::none()),
+            )
+        },
+    })]
+}
+
+pub fn check_input(input: &str) -> CheckResult {
+    let mut first_line = true;
+
+    let mut errors = input::check_lines(input, |line| {
+        if !std::mem::replace(&mut first_line, false) {
+            return Err("expected a single line of starting numbers".to_string());
+        }
+
+        for v in line.split(',') {
+            input::parse::<u32>(v)?;
+        }
+
+        Ok(())
+    });
+
+    input::check_no_trailing_newline(input, &mut errors);
+
+    input::validated(errors, || {
+        input
+            .split(',')
+            .map(|v| v.parse().unwrap())
+            .collect::<Vec<u32>>()
+            .to_json()
+    })
+}
+
+pub const SOLUTION: Solution = Solution {
+    day: 15,
+    input_path: INPUT_PATH,
+    version: 2,
+    examples: &EXAMPLES,
+    params: &[
+        Param {
+            name: "part1_turns",
+            default: "2020",
+            help: "turn of the number to find in part 1, from 1",
+            valid: |turns| turns.parse::<u32>().is_ok_and(|turns| turns >= 1),
+            fuzz: None,
+        },
+        Param {
+            name: "part2_turns",
+            default: "30000000",
+            help: "turn of the number to find in part 2, from 1",
+            valid: |turns| turns.parse::<u32>().is_ok_and(|turns| turns >= 1),
+            fuzz: Some("2020"),
+        },
+    ],
+    check_input,
+    part1: |input, params, progress| solve(input, params.get("part1_turns"), progress).to_string(),
+    part2: Some(|input, params, progress| {
+        solve(input, params.get("part2_turns"), progress).to_string()
+    }),
+    variants: &[Variant {
+        name: "vec",
+        part1: Some(|input, params, progress| {
+            solve_vec(input, params.get("part1_turns"), progress).to_string()
+        }),
+        part2: Some(|input, params, progress| {
+            solve_vec(input, params.get("part2_turns"), progress).to_string()
+        }),
+    }],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::Params;
+    use crate::vault;
+
+    #[test]
+    fn day15_part1() {
+        assert_eq!(solve("0,3,6", 10, &Progress::none()), 0);
+        assert_eq!(solve("1,3,2", 2020, &Progress::none()), 1);
+        assert_eq!(solve("2,1,3", 2020, &Progress::none()), 10);
+        assert_eq!(solve("1,2,3", 2020, &Progress::none()), 27);
+        assert_eq!(solve("2,3,1", 2020, &Progress::none()), 78);
+        assert_eq!(solve("3,2,1", 2020, &Progress::none()), 438);
+        assert_eq!(solve("3,1,2", 2020, &Progress::none()), 1836);
+
+        // Within the starting numbers
+        assert_eq!(solve("0,3,6", 1, &Progress::none()), 0);
+        assert_eq!(solve("0,3,6", 2, &Progress::none()), 3);
+        assert_eq!(solve_vec("0,3,6", 3, &Progress::none()), 6);
+
+        // Starting numbers beyond the turns are not tabled
+        assert_eq!(solve_vec("4294967295,0", 4, &Progress::none()), 1);
+        assert_eq!(solve_vec("4294967295,0", 5, &Progress::none()), 0);
+    }
+
+    #[test]
+    fn day15_turns() {
+        let turns = |value: &str| Params::new(SOLUTION.params, &[format!("part1_turns={}", value)]);
+
+        assert!(turns("1").is_ok());
+        assert_eq!(
+            turns("0"),
+            Err(
+                "invalid value `0` for parameter `part1_turns` (turn of the number to find in part 1, from 1)"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day15_part1_input() {
+        assert_eq!(
+            solve(&vault::read(INPUT_PATH).unwrap(), 2020, &Progress::none()),
+            1238
+        );
+    }
+
+    // Even the examples take 30 million turns
+    #[test]
+    #[cfg_attr(not(feature = "slow-tests"), ignore = "slow in debug builds")]
+    fn day15_part2() {
+        assert_eq!(solve("0,3,6", 30000000, &Progress::none()), 175594);
+        assert_eq!(solve("1,3,2", 30000000, &Progress::none()), 2578);
+        assert_eq!(solve("2,1,3", 30000000, &Progress::none()), 3544142);
+        assert_eq!(solve("1,2,3", 30000000, &Progress::none()), 261214);
+        assert_eq!(solve("2,3,1", 30000000, &Progress::none()), 6895259);
+        assert_eq!(solve("3,2,1", 30000000, &Progress::none()), 18);
+        assert_eq!(solve("3,1,2", 30000000, &Progress::none()), 362);
+        assert_eq!(
+            solve(
+                &vault::read(INPUT_PATH).unwrap(),
+                30000000,
+                &Progress::none()
+            ),
+            3745954
+        );
+    }
+
+    #[test]
+    fn day15_vec() {
+        for (example, expected) in EXAMPLES.iter().zip([436, 1, 10, 27, 78, 438, 1836]) {
+            assert_eq!(solve_vec(example, PART1_TURNS, &Progress::none()), expected);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day15_vec_input() {
+        let input = vault::read(INPUT_PATH).unwrap();
+        assert_eq!(solve_vec(&input, PART1_TURNS, &Progress::none()), 1238);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "slow-tests"), ignore = "slow in debug builds")]
+    fn day15_vec_part2() {
+        let input = vault::read(INPUT_PATH).unwrap();
+        assert_eq!(solve_vec(&input, PART2_TURNS, &Progress::none()), 3745954);
+    }
+}
@@ -0,0 +1,473 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+
+use crate::budget;
+use crate::explain;
+use crate::input::{self, CheckResult, InputError};
+use crate::json::{Json, ToJson};
+use crate::params::Param;
+use crate::solution::{Solution, Variant};
+
+pub const INPUT_PATH: &str = "src/day16/input.txt";
+pub const PREFIX: &str = "departure";
+
+// From the puzzle statement
+const EXAMPLE: &str = "class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12";
+
+#[derive(Debug)]
+struct Field<'a> {
+    class: &'a str,
+    ranges: Vec<RangeInclusive<u32>>,
+}
+
+struct ParsedInput<'a> {
+    fields: Vec<Field<'a>>,
+    myticket: Vec<u32>,
+    nearby_valids: Vec<Vec<u32>>,
+    nearby_invalids: Vec<(Vec<u32>, u32)>,
+}
+
+fn parse_input(input: &str) -> ParsedInput<'_> {
+    let v: Vec<_> = input.split("\n\n").collect();
+
+    let str_fields = v[0];
+    let str_myticket = &v[1][13..];
+    let str_nearby_tickets = &v[2][16..];
+
+    // Parse fields
+    let mut fields: Vec<Field> = Vec::new();
+
+    for field in str_fields.lines() {
+        let v: Vec<_> = field.split(": ").collect();
+
+        let class = v[0];
+
+        let ranges: Vec<_> = v[1]
+            .split(" or ")
+            .map(|x| {
+                let values: Vec<u32> = x.split('-').map(|x| x.parse().unwrap()).collect();
+
+                RangeInclusive::new(values[0], values[1])
+            })
+            .collect();
+
+        fields.push(Field { class, ranges });
+    }
+
+    // Parse my ticket
+    let myticket: Vec<u32> = str_myticket
+        .split(',')
+        .map(|x| x.parse().unwrap())
+        .collect();
+
+    // Parse nearby fields
+    let mut nearby_valids: Vec<Vec<u32>> = vec![];
+    let mut nearby_invalids: Vec<(Vec<u32>, u32)> = vec![];
+
+    for l in str_nearby_tickets.lines() {
+        let ticket: Vec<u32> = l.split(',').map(|x| x.parse().unwrap()).collect();
+        let mut invalid_field: Option<u32> = None;
+
+        for v in &ticket {
+            let mut found = false;
+
+            for field in &fields {
+                let valid = field
+                    .ranges
+                    .iter()
+                    .filter(|range| range.contains(v))
+                    .count()
+                    > 0;
+
+                if valid {
+                    found = true;
+                    break;
+                }
+            }
+
+            if !found {
+                invalid_field = Some(*v);
+                break;
+            }
+        }
+
+        if let Some(invalid_field) = invalid_field {
+            nearby_invalids.push((ticket, invalid_field));
+        } else {
+            nearby_valids.push(ticket);
+        }
+    }
+
+    ParsedInput {
+        fields,
+        myticket,
+        nearby_valids,
+        nearby_invalids,
+    }
+}
+
+pub fn part1(input: &str) -> u32 {
+    let input = parse_input(input);
+
+    input
+        .nearby_invalids
+        .iter()
+        .map(|(_, invalid_field)| invalid_field)
+        .sum()
+}
+
+pub fn part2(input: &str, prefix: &str) -> u64 {
+    let input = parse_input(input);
+    let mut remaining_fields: HashSet<_> = (0..input.fields.len()).collect();
+    let mut remaining_columns: HashSet<_> = (0..input.fields.len()).collect();
+
+    let mut field_map: HashMap<usize, usize> = HashMap::new();
+
+    while !remaining_columns.is_empty() {
+        budget::checkpoint("column search", || {
+            format!("{} columns left", remaining_columns.len())
+        });
+
+        let mut found_fields: Vec<(usize, usize, &Field)> = vec![];
+
+        for col_idx in &remaining_columns {
+            let values: Vec<u32> = input
+                .nearby_valids
+                .iter()
+                .map(|ticket| ticket[*col_idx])
+                .collect();
+
+            let mut fields_scores: Vec<(usize, usize, &Field)> = vec![];
+
+            for field_idx in &remaining_fields {
+                let field = &input.fields[*field_idx];
+
+                let matches = values.iter().all(|v| {
+                    field
+                        .ranges
+                        .iter()
+                        .filter(|range| range.contains(v))
+                        .count()
+                        == 1
+                });
+
+                if matches {
+                    fields_scores.push((*field_idx, *col_idx, field));
+                }
+            }
+
+            if fields_scores.len() == 1 {
+                found_fields.push(fields_scores[0]);
+            }
+        }
+
+        for (field_idx, col_idx, _) in found_fields {
+            field_map.insert(field_idx, col_idx);
+
+            remaining_fields.remove(&field_idx);
+            remaining_columns.remove(&col_idx);
+        }
+    }
+
+    explain::note("field_map", || {
+        Json::object(
+            input
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(idx, field)| (field.class, field_map[&idx].to_json())),
+        )
+    });
+
+    input
+        .fields
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, field)| {
+            if field.class.starts_with(prefix) {
+                Some(input.myticket[*field_map.get(&idx).unwrap()] as u64)
+            } else {
+                None
+            }
+        })
+        .product()
+}
+
+impl ToJson for Field<'_> {
+    fn to_json(&self) -> Json {
+        let ranges: Vec<_> = self
+            .ranges
+            .iter()
+            .map(|range| vec![*range.start(), *range.end()])
+            .collect();
+
+        Json::object([
+            ("class", self.class.to_json()),
+            ("ranges", ranges.to_json()),
+        ])
+    }
+}
+
+impl ToJson for ParsedInput<'_> {
+    fn to_json(&self) -> Json {
+        let nearby_invalids: Vec<_> = self
+            .nearby_invalids
+            .iter()
+            .map(|(ticket, invalid_field)| {
+                Json::object([
+                    ("ticket", ticket.to_json()),
+                    ("invalid_field", invalid_field.to_json()),
+                ])
+            })
+            .collect();
+
+        Json::object([
+            ("fields", self.fields.to_json()),
+            ("myticket", self.myticket.to_json()),
+            ("nearby_valids", self.nearby_valids.to_json()),
+            ("nearby_invalids", Json::Array(nearby_invalids)),
+        ])
+    }
+}
+
+// Same as `part2`, pairing the fields with the columns through a bipartite matching instead
+// of settling the columns left with a single candidate field
+pub fn part2_matching(input: &str, prefix: &str) -> u64 {
+    let input = parse_input(input);
+    let columns = input.fields.len();
+
+    let candidates: Vec<Vec<usize>> = input
+        .fields
+        .iter()
+        .map(|field| {
+            (0..columns)
+                .filter(|&col| {
+                    input.nearby_valids.iter().all(|ticket| {
+                        field
+                            .ranges
+                            .iter()
+                            .any(|range| range.contains(&ticket[col]))
+                    })
+                })
+                .collect()
+        })
+        .collect();
+
+    // Kuhn's algorithm, looking for an augmenting path from each field in turn
+    fn augment(
+        field: usize,
+        candidates: &[Vec<usize>],
+        column_fields: &mut [Option<usize>],
+        visited: &mut [bool],
+    ) -> bool {
+        for &col in &candidates[field] {
+            if std::mem::replace(&mut visited[col], true) {
+                continue;
+            }
+
+            let free = match column_fields[col] {
+                None => true,
+                Some(other) => augment(other, candidates, column_fields, visited),
+            };
+
+            if free {
+                column_fields[col] = Some(field);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    let mut column_fields = vec![None; columns];
+    for field in 0..input.fields.len() {
+        augment(
+            field,
+            &candidates,
+            &mut column_fields,
+            &mut vec![false; columns],
+        );
+    }
+
+    column_fields
+        .iter()
+        .enumerate()
+        .filter_map(|(col, field)| {
+            let field = &input.fields[field.expect("no matching of the fields and the columns")];
+            field
+                .class
+                .starts_with(prefix)
+                .then(|| input.myticket[col] as u64)
+        })
+        .product()
+}
+
+fn check_field(line: &str) -> Result<(), String> {
+    let (_, ranges) = line
+        .split_once(": ")
+        .ok_or("expected `<field>: <start>-<end> or <start>-<end>`")?;
+
+    for range in ranges.split(" or ") {
+        let (start, end) = range
+            .split_once('-')
+            .ok_or_else(|| format!("invalid range `{}`", range))?;
+        let (start, end): (u32, u32) = (input::parse(start)?, input::parse(end)?);
+
+        if start > end {
+            return Err(format!("empty range `{}`", range));
+        }
+    }
+
+    Ok(())
+}
+
+fn check_ticket(line: &str, fields_count: usize) -> Result<(), String> {
+    let values = line
+        .split(',')
+        .map(input::parse::<u32>)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if values.len() != fields_count {
+        return Err(format!(
+            "expected {} values, got {}",
+            fields_count,
+            values.len()
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn check_input(input: &str) -> CheckResult {
+    let mut errors = vec![];
+
+    // Sections are fields, my ticket and nearby tickets, separated by a blank line
+    let mut section = 0;
+    let mut section_line = 0;
+    let mut fields_count = 0;
+
+    for (idx, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            section += 1;
+            section_line = 0;
+            continue;
+        }
+
+        section_line += 1;
+
+        let result = match (section, section_line) {
+            (0, _) => {
+                fields_count += 1;
+                check_field(line)
+            }
+            (1, 1) if line != "your ticket:" => Err("expected `your ticket:`".to_string()),
+            (2, 1) if line != "nearby tickets:" => Err("expected `nearby tickets:`".to_string()),
+            (1, 1) | (2, 1) => Ok(()),
+            (1, 2) | (2, _) => check_ticket(line, fields_count),
+            (1, _) => Err("expected a single ticket".to_string()),
+            _ => Err("unexpected section".to_string()),
+        };
+
+        if let Err(message) = result {
+            errors.push(InputError::new(idx + 1, line, message));
+        }
+    }
+
+    if section < 2 {
+        let line = input.lines().count() + 1;
+        errors.push(InputError::new(line, "", "missing ticket sections"));
+    }
+
+    input::check_no_trailing_newline(input, &mut errors);
+
+    input::validated(errors, || parse_input(input).to_json())
+}
+
+pub const SOLUTION: Solution = Solution {
+    day: 16,
+    input_path: INPUT_PATH,
+    version: 1,
+    examples: &[EXAMPLE],
+    params: &[Param {
+        name: "prefix",
+        default: PREFIX,
+        help: "prefix of the fields multiplied in part 2",
+        valid: |_| true,
+        fuzz: None,
+    }],
+    check_input,
+    part1: |input, _, _| part1(input).to_string(),
+    part2: Some(|input, params, _| part2(input, params.value("prefix")).to_string()),
+    variants: &[Variant {
+        name: "matching",
+        part1: None,
+        part2: Some(|input, params, _| part2_matching(input, params.value("prefix")).to_string()),
+    }],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault;
+
+    #[test]
+    fn day16_part1() {
+        assert_eq!(part1(EXAMPLE), 71);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day16_part1_input() {
+        assert_eq!(part1(&vault::read(INPUT_PATH).unwrap()), 27802);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day16_part2() {
+        assert_eq!(
+            part2(&vault::read(INPUT_PATH).unwrap(), PREFIX),
+            279139880759
+        );
+        assert_eq!(
+            part2_matching(&vault::read(INPUT_PATH).unwrap(), PREFIX),
+            279139880759
+        );
+    }
+
+    #[test]
+    fn day16_check_input() {
+        const INPUT: &str = "class: 1-3 or 5-7
+row: 6-11 or x
+
+your ticket:
+7,1
+
+nearby tickets:
+7,3,47
+40,4";
+
+        let errors = check_input(INPUT).unwrap_err();
+        assert_eq!(
+            errors.iter().map(|e| e.line).collect::<Vec<_>>(),
+            vec![2, 8]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day16_check_puzzle_input() {
+        let parsed = check_input(&vault::read(INPUT_PATH).unwrap()).unwrap();
+        assert!(matches!(parsed.get("fields"), Some(Json::Array(fields)) if fields.len() == 20));
+    }
+}
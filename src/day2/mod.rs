@@ -0,0 +1,141 @@
+use crate::input::{self, CheckResult};
+use crate::json::{Json, ToJson};
+use crate::solution::Solution;
+
+pub const INPUT_PATH: &str = "src/day2/input.txt";
+
+// From the puzzle statement
+const EXAMPLE: &str = "1-3 a: abcde
+1-3 b: cdefg
+2-9 c: ccccccccc";
+
+type Checker = fn(password: &str, c: char, val_one: u32, val_two: u32) -> bool;
+
+fn check_passwords(input: &str, checker: Checker) -> u32 {
+    let mut valid_count = 0;
+
+    for l in input.lines() {
+        // Get password
+        let t: Vec<&str> = l.split(": ").collect();
+        let password = t[1];
+
+        // Get char policy
+        let t: Vec<&str> = t[0].split(' ').collect();
+        let c = t[1].chars().next().unwrap();
+
+        // Get char count policy
+        let t: Vec<u32> = t[0].split('-').map(|x| x.parse().unwrap()).collect();
+        let (val_one, val_two) = (t[0], t[1]);
+
+        // Check
+        if checker(password, c, val_one, val_two) {
+            valid_count += 1;
+        }
+    }
+
+    valid_count
+}
+
+pub fn part1(input: &str) -> u32 {
+    let checker = |password: &str, c: char, val_one: u32, val_two: u32| {
+        let char_count = password.chars().filter(|&x| x == c).count() as u32;
+        (val_one..val_two + 1).contains(&char_count)
+    };
+
+    check_passwords(input, checker)
+}
+
+pub fn part2(input: &str) -> u32 {
+    let checker = |password: &str, c: char, val_one: u32, val_two: u32| {
+        let v = [
+            password.chars().nth((val_one - 1) as usize).unwrap(),
+            password.chars().nth((val_two - 1) as usize).unwrap(),
+        ];
+
+        v.iter().filter(|&&x| x == c).count() == 1
+    };
+
+    check_passwords(input, checker)
+}
+
+pub fn check_input(input: &str) -> CheckResult {
+    let mut entries = vec![];
+
+    let errors = input::check_lines(input, |line| {
+        let (policy, password) = line
+            .split_once(": ")
+            .ok_or("expected `<min>-<max> <letter>: <password>`")?;
+        let (range, letter) = policy
+            .split_once(' ')
+            .ok_or("expected `<min>-<max> <letter>`")?;
+        let (val_one, val_two) = range.split_once('-').ok_or("expected `<min>-<max>`")?;
+        let (val_one, val_two): (u32, u32) = (input::parse(val_one)?, input::parse(val_two)?);
+
+        if letter.chars().count() != 1 {
+            return Err(format!("expected a single letter, got `{}`", letter));
+        }
+
+        if val_one == 0 || val_one > val_two {
+            return Err(format!("invalid policy range {}-{}", val_one, val_two));
+        }
+
+        // Part 2 reads the letters at both positions
+        if val_two as usize > password.chars().count() {
+            return Err(format!(
+                "position {} is past the end of the password",
+                val_two
+            ));
+        }
+
+        entries.push(Json::object([
+            ("val_one", val_one.to_json()),
+            ("val_two", val_two.to_json()),
+            ("letter", letter.to_json()),
+            ("password", password.to_json()),
+        ]));
+
+        Ok(())
+    });
+
+    input::validated(errors, || Json::Array(entries))
+}
+
+pub const SOLUTION: Solution = Solution {
+    day: 2,
+    input_path: INPUT_PATH,
+    version: 1,
+    examples: &[EXAMPLE],
+    params: &[],
+    check_input,
+    part1: |input, _, _| part1(input).to_string(),
+    part2: Some(|input, _, _| part2(input).to_string()),
+    variants: &[],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault;
+
+    #[test]
+    fn day2_part1() {
+        assert_eq!(part1(EXAMPLE), 2);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day2_part1_input() {
+        assert_eq!(part1(&vault::read(INPUT_PATH).unwrap()), 469);
+    }
+
+    #[test]
+    fn day2_part2() {
+        assert_eq!(part2(EXAMPLE), 1);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day2_part2_input() {
+        assert_eq!(part2(&vault::read(INPUT_PATH).unwrap()), 267);
+    }
+}
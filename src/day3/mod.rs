@@ -0,0 +1,202 @@
+use crate::explain;
+use crate::input::{self, CheckResult};
+use crate::json::{Json, ToJson};
+use crate::params::Param;
+use crate::solution::Solution;
+
+pub const INPUT_PATH: &str = "src/day3/input.txt";
+pub const SLOPES: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+// From the puzzle statement
+const EXAMPLE: &str = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+fn parse_input(input: &str) -> Vec<Vec<bool>> {
+    let mut map = vec![];
+
+    for str_l in input.lines() {
+        let mut line = vec![false; str_l.len()];
+
+        for (idx, c) in str_l.chars().enumerate() {
+            if c == '#' {
+                line[idx] = true;
+            }
+        }
+
+        map.push(line);
+    }
+
+    map
+}
+
+fn count_trees(map: &[Vec<bool>], right: usize, down: usize) -> u32 {
+    let line_size = map[0].len();
+    let mut count = 0;
+    let mut x: usize = 0;
+    let mut y: usize = 0;
+
+    loop {
+        x = (x + right) % line_size;
+        y += down;
+
+        if y >= map.len() {
+            break;
+        }
+
+        if map[y][x] {
+            count += 1;
+        }
+    }
+
+    count
+}
+
+// Marks the squares visited along a slope, `O` for open squares and `X` for trees
+pub fn render_path(input: &str, right: usize, down: usize) -> String {
+    let map = parse_input(input);
+    let mut out = String::new();
+
+    for (y, line) in map.iter().enumerate() {
+        let visited = (y % down == 0).then(|| (y / down * right) % line.len());
+
+        for (x, &tree) in line.iter().enumerate() {
+            out.push(match (visited == Some(x), tree) {
+                (true, false) => 'O',
+                (true, true) => 'X',
+                (false, false) => '.',
+                (false, true) => '#',
+            });
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+pub fn part1(input: &str) -> u32 {
+    let map = parse_input(input);
+    count_trees(&map, 3, 1)
+}
+
+pub fn part2(input: &str, slopes: &[(usize, usize)]) -> u32 {
+    let map = parse_input(input);
+    let mut result = 1;
+
+    for &(right, down) in slopes {
+        let trees = count_trees(&map, right, down);
+        explain::note("slope", || {
+            Json::object([
+                ("right", right.to_json()),
+                ("down", down.to_json()),
+                ("trees", trees.to_json()),
+            ])
+        });
+
+        result *= trees;
+    }
+
+    result
+}
+
+// Slopes are written `right:down`, comma separated
+pub fn parse_slopes(s: &str) -> Option<Vec<(usize, usize)>> {
+    s.split(',')
+        .map(|slope| {
+            let (right, down) = slope.split_once(':')?;
+            let down = down.parse().ok().filter(|&down| down > 0)?;
+            Some((right.parse().ok()?, down))
+        })
+        .collect()
+}
+
+pub fn check_input(input: &str) -> CheckResult {
+    let mut width = None;
+
+    let errors = input::check_lines(input, |line| {
+        if let Some(c) = line.chars().find(|&c| c != '.' && c != '#') {
+            return Err(format!("unexpected character `{}`", c));
+        }
+
+        match width {
+            _ if line.is_empty() => Err("empty row".to_string()),
+            None => {
+                width = Some(line.len());
+                Ok(())
+            }
+            Some(width) if width != line.len() => {
+                Err(format!("expected {} columns, got {}", width, line.len()))
+            }
+            Some(_) => Ok(()),
+        }
+    });
+
+    input::validated(errors, || parse_input(input).to_json())
+}
+
+pub const SOLUTION: Solution = Solution {
+    day: 3,
+    input_path: INPUT_PATH,
+    version: 1,
+    examples: &[EXAMPLE],
+    params: &[Param {
+        name: "slopes",
+        default: "1:1,3:1,5:1,7:1,1:2",
+        help: "slopes of part 2, as `right:down` pairs",
+        valid: |slopes| parse_slopes(slopes).is_some(),
+        fuzz: None,
+    }],
+    check_input,
+    part1: |input, _, _| part1(input).to_string(),
+    part2: Some(|input, params, _| {
+        part2(input, &parse_slopes(params.value("slopes")).unwrap()).to_string()
+    }),
+    variants: &[],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot;
+    use crate::vault;
+
+    #[test]
+    fn day3_part1() {
+        assert_eq!(part1(EXAMPLE), 7);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day3_part1_input() {
+        assert_eq!(part1(&vault::read(INPUT_PATH).unwrap()), 167);
+    }
+
+    #[test]
+    fn day3_part2() {
+        assert_eq!(part2(EXAMPLE, &SLOPES), 336);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day3_part2_input() {
+        assert_eq!(part2(&vault::read(INPUT_PATH).unwrap(), &SLOPES), 736527114);
+    }
+
+    #[test]
+    fn day3_render_path() {
+        for (right, down) in SLOPES {
+            snapshot::assert_snapshot!(
+                &format!("path_{}_{}", right, down),
+                render_path(EXAMPLE, right, down)
+            );
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::input::{self, CheckResult};
+use crate::json::{Json, ToJson};
+use crate::property::{self, Property, PropertyTest, Rng, Shrink};
+use crate::solution::Solution;
+
+pub const INPUT_PATH: &str = "src/day4/input.txt";
+
+// From the puzzle statement
+const EXAMPLE1: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in";
+
+const EXAMPLE2: &str = "eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007
+
+pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
+
+const REQUIRED_FIELDS: usize = 7;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Field {
+    name: String,
+    data: String,
+}
+
+impl Field {
+    fn new(s: &str) -> Self {
+        let fields: Vec<&str> = s.split(':').collect();
+
+        Self {
+            name: String::from(fields[0]),
+            data: String::from(fields[1]),
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.name, self.data)
+    }
+}
+
+impl Shrink for Field {
+    fn shrink(&self) -> Vec<Self> {
+        self.data
+            .shrink()
+            .into_iter()
+            .map(|data| Field {
+                name: self.name.clone(),
+                data,
+            })
+            .collect()
+    }
+}
+
+fn parse_passports(input: &str) -> Vec<Vec<Field>> {
+    let mut passports = vec![];
+
+    for raw_passport in input.split("\n\n") {
+        let raw_passport = str::replace(raw_passport, "\n", " ");
+        passports.push(raw_passport.split(' ').map(Field::new).collect());
+    }
+
+    passports
+}
+
+fn read_passports(input: &str) -> Vec<Vec<Field>> {
+    let passports = parse_passports(input);
+
+    let passport_valid =
+        |fields: &Vec<Field>| fields.iter().filter(|&x| x.name != "cid").count() == REQUIRED_FIELDS;
+
+    passports
+        .iter()
+        .filter(|x: &&Vec<Field>| passport_valid(x))
+        .cloned()
+        .collect()
+}
+
+// Fields are wrapped every few fields, like in the puzzle input
+fn render(passports: &[Vec<Field>]) -> String {
+    passports
+        .iter()
+        .map(|fields| {
+            fields
+                .chunks(4)
+                .map(|line| {
+                    line.iter()
+                        .map(Field::to_string)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn generate(rng: &mut Rng) -> Vec<Vec<Field>> {
+    const CHARS: &[u8] = b"0123456789abcdef#cmin";
+
+    (0..1 + rng.below(5))
+        .map(|_| {
+            (0..1 + rng.below(8))
+                .map(|_| Field {
+                    name: KNOWN_FIELDS[rng.below(KNOWN_FIELDS.len() as u64) as usize].to_string(),
+                    data: (0..1 + rng.below(10))
+                        .map(|_| CHARS[rng.below(CHARS.len() as u64) as usize] as char)
+                        .collect(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// Empty passports or values cannot be told apart from the separators
+fn is_valid_batch(passports: &[Vec<Field>]) -> bool {
+    !passports.is_empty()
+        && passports
+            .iter()
+            .all(|fields| !fields.is_empty() && fields.iter().all(|f| !f.data.is_empty()))
+}
+
+pub fn properties() -> Vec<Box<dyn PropertyTest>> {
+    vec![Box::new(Property {
+        name: "day4 passport round trip",
+        generate,
+        precondition: |passports| is_valid_batch(passports),
+        holds: |passports| property::expect_eq(passports, &parse_passports(&render(passports))),
+    })]
+}
+
+pub fn part1(input: &str) -> u32 {
+    read_passports(input).len() as u32
+}
+
+pub fn part2(input: &str) -> u32 {
+    let passports = read_passports(input);
+
+    let int_in_range = |v: usize, begin: usize, end: usize| (begin..end + 1).contains(&v);
+
+    let int_field_valid = |field: &Field, begin: usize, end: usize| {
+        int_in_range(field.data.parse().unwrap(), begin, end)
+    };
+
+    let hcl_map = HashSet::from([
+        '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'a', 'b', 'c', 'd', 'e', 'f',
+    ]);
+
+    let ecl_map = HashSet::from(["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]);
+
+    let pid_map = HashSet::from(['0', '1', '2', '3', '4', '5', '6', '7', '8', '9']);
+
+    let field_valid = |field: &Field| match field.name.as_str() {
+        "byr" => int_field_valid(field, 1920, 2002),
+        "iyr" => int_field_valid(field, 2010, 2020),
+        "eyr" => int_field_valid(field, 2020, 2030),
+        "hgt" => {
+            let get_value = || -> usize { field.data[0..field.data.len() - 2].parse().unwrap() };
+
+            if field.data.ends_with("cm") {
+                int_in_range(get_value(), 150, 193)
+            } else if field.data.ends_with("in") {
+                int_in_range(get_value(), 59, 76)
+            } else {
+                false
+            }
+        }
+        "hcl" => {
+            field.data.starts_with('#')
+                && field
+                    .data
+                    .chars()
+                    .skip(1)
+                    .filter(|x| !hcl_map.contains(x))
+                    .count()
+                    == 0
+        }
+        "ecl" => ecl_map.contains(field.data.as_str()),
+        "pid" => {
+            field.data.len() == 9
+                && field.data.chars().filter(|x| !pid_map.contains(x)).count() == 0
+        }
+        "cid" => false,
+        _ => {
+            panic!("Unexpected field {}", field.name);
+        }
+    };
+
+    let passport_valid =
+        |fields: &Vec<Field>| fields.iter().filter(|&x| field_valid(x)).count() == REQUIRED_FIELDS;
+
+    passports
+        .iter()
+        .filter(|x: &&Vec<Field>| passport_valid(x))
+        .count() as u32
+}
+
+const KNOWN_FIELDS: [&str; 8] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+
+pub fn check_input(input: &str) -> CheckResult {
+    let mut seen: HashSet<String> = HashSet::new();
+
+    let mut errors = input::check_lines(input, |line| {
+        // A blank line starts a new passport
+        if line.is_empty() {
+            seen.clear();
+            return Ok(());
+        }
+
+        for token in line.split(' ') {
+            let (name, data) = token
+                .split_once(':')
+                .ok_or_else(|| format!("expected `<field>:<value>`, got `{}`", token))?;
+
+            if !KNOWN_FIELDS.contains(&name) {
+                return Err(format!("unknown field `{}`", name));
+            }
+
+            if !seen.insert(name.to_string()) {
+                return Err(format!("duplicate field `{}`", name));
+            }
+
+            // Part 2 parses these as numbers
+            match name {
+                "byr" | "iyr" | "eyr" => {
+                    input::parse::<usize>(data)?;
+                }
+                "hgt" => {
+                    if let Some(value) = data.strip_suffix("cm").or_else(|| data.strip_suffix("in"))
+                    {
+                        input::parse::<usize>(value)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    });
+
+    input::check_no_trailing_newline(input, &mut errors);
+
+    input::validated(errors, || {
+        Json::Array(
+            parse_passports(input)
+                .iter()
+                .map(|fields| {
+                    Json::object(fields.iter().map(|f| (f.name.as_str(), f.data.to_json())))
+                })
+                .collect(),
+        )
+    })
+}
+
+pub const SOLUTION: Solution = Solution {
+    day: 4,
+    input_path: INPUT_PATH,
+    version: 1,
+    examples: &[EXAMPLE1, EXAMPLE2],
+    params: &[],
+    check_input,
+    part1: |input, _, _| part1(input).to_string(),
+    part2: Some(|input, _, _| part2(input).to_string()),
+    variants: &[],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault;
+
+    #[test]
+    fn day4_part1() {
+        assert_eq!(part1(EXAMPLE1), 2);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day4_part1_input() {
+        assert_eq!(part1(&vault::read(INPUT_PATH).unwrap()), 250);
+    }
+
+    #[test]
+    fn day4_part2() {
+        assert_eq!(part2(EXAMPLE2), 4);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day4_part2_input() {
+        assert_eq!(part2(&vault::read(INPUT_PATH).unwrap()), 158);
+    }
+}
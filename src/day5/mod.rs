@@ -0,0 +1,166 @@
+use std::cmp::{max, min};
+
+use crate::explain;
+use crate::input::{self, CheckResult};
+use crate::json::ToJson;
+use crate::property::{self, Property, PropertyTest};
+use crate::solution::Solution;
+
+pub const INPUT_PATH: &str = "src/day5/input.txt";
+
+// From the puzzle statement
+const EXAMPLE: &str = "BFFFBBFRRR
+FFFBBBFRRR
+BBFFBBFRLL";
+
+fn convert<T>(i: T, one: char) -> u32
+where
+    T: Iterator<Item = char>,
+{
+    i.fold(0, |acc, c| (acc << 1) | ((c == one) as u32))
+}
+
+fn get_seat_id(input: &str) -> u32 {
+    let row = convert(input.chars().take(7), 'B');
+    let column = convert(input.chars().skip(7), 'R');
+
+    row * 8 + column
+}
+
+// Inverse of get_seat_id, the seat ID being a 10 bits binary number
+fn encode_seat(id: u32) -> String {
+    (0..10)
+        .rev()
+        .map(|bit| {
+            let one = id >> bit & 1 == 1;
+            match (bit >= 3, one) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R',
+            }
+        })
+        .collect()
+}
+
+pub fn properties() -> Vec<Box<dyn PropertyTest>> {
+    vec![Box::new(Property {
+        name: "day5 seat id round trip",
+        generate: |rng| rng.below(1024) as u32,
+        precondition: |_| true,
+        holds: |&id| property::expect_eq(id, get_seat_id(&encode_seat(id))),
+    })]
+}
+
+// Seat map from the first to the last occupied row, `#` for the seats in the input
+pub fn render_seats(input: &str) -> String {
+    let mut taken = [[false; 8]; 128];
+    for id in input.lines().map(get_seat_id) {
+        taken[id as usize / 8][id as usize % 8] = true;
+    }
+
+    let occupied: Vec<usize> = (0..128).filter(|&row| taken[row].contains(&true)).collect();
+    let (Some(&first), Some(&last)) = (occupied.first(), occupied.last()) else {
+        return String::new();
+    };
+
+    let mut out = String::new();
+    for (row, seats) in taken.iter().enumerate().take(last + 1).skip(first) {
+        let seats: String = seats.iter().map(|&x| if x { '#' } else { '.' }).collect();
+        out.push_str(&format!("{:3} {} {}\n", row, &seats[..4], &seats[4..]));
+    }
+
+    out
+}
+
+pub fn part1(input: &str) -> u32 {
+    input.lines().map(get_seat_id).max().unwrap()
+}
+
+pub fn part2(input: &str) -> u32 {
+    let (seat_start, seat_end, sum) = input
+        .lines()
+        .filter_map(|x| {
+            const VALID_START: u32 = 8;
+            const VALID_END: u32 = (8 * 127) - 1;
+
+            let seat = get_seat_id(x);
+            if (VALID_START..=VALID_END).contains(&seat) {
+                Some(seat)
+            } else {
+                None
+            }
+        })
+        .fold(
+            (u32::MAX, u32::MIN, 0),
+            |(seat_start, seat_end, sum), seat| {
+                (min(seat_start, seat), max(seat_end, seat), sum + seat)
+            },
+        );
+
+    explain::note("seat_range", || vec![seat_start, seat_end].to_json());
+
+    let sum_cb = |n| (n * (n + 1)) / 2;
+    sum_cb(seat_end) - sum_cb(seat_start - 1) - sum
+}
+
+pub fn check_input(input: &str) -> CheckResult {
+    let errors = input::check_lines(input, |line| {
+        let valid = line.len() == 10
+            && line.chars().take(7).all(|c| c == 'F' || c == 'B')
+            && line.chars().skip(7).all(|c| c == 'L' || c == 'R');
+
+        if valid {
+            Ok(())
+        } else {
+            Err("expected 7 `F`/`B` followed by 3 `L`/`R`".to_string())
+        }
+    });
+
+    input::validated(errors, || {
+        input.lines().map(get_seat_id).collect::<Vec<_>>().to_json()
+    })
+}
+
+pub const SOLUTION: Solution = Solution {
+    day: 5,
+    input_path: INPUT_PATH,
+    version: 1,
+    examples: &[EXAMPLE],
+    params: &[],
+    check_input,
+    part1: |input, _, _| part1(input).to_string(),
+    part2: Some(|input, _, _| part2(input).to_string()),
+    variants: &[],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot;
+    use crate::vault;
+
+    #[test]
+    fn day5_part1() {
+        assert_eq!(get_seat_id("BFFFBBFRRR"), 567);
+        assert_eq!(get_seat_id("FFFBBBFRRR"), 119);
+        assert_eq!(get_seat_id("BBFFBBFRLL"), 820);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day5_part1_input() {
+        assert_eq!(part1(&vault::read(INPUT_PATH).unwrap()), 864);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day5_part2() {
+        assert_eq!(part2(&vault::read(INPUT_PATH).unwrap()), 739);
+    }
+
+    #[test]
+    fn day5_render_seats() {
+        snapshot::assert_snapshot!("seats", render_seats(EXAMPLE));
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::input::{self, CheckResult};
+use crate::json::{Json, ToJson};
+use crate::solution::Solution;
+
+pub const INPUT_PATH: &str = "src/day6/input.txt";
+
+// From the puzzle statement
+const EXAMPLE: &str = "abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b";
+
+pub fn part1(input: &str) -> u32 {
+    let mut count = 0;
+
+    for group in input.split("\n\n").map(|x| x.to_string()) {
+        count += group
+            .replace("\n", "")
+            .chars()
+            .collect::<HashSet<char>>()
+            .len() as u32;
+    }
+
+    count
+}
+
+pub fn part2(input: &str) -> u32 {
+    let mut count = 0;
+
+    for group in input.split("\n\n").map(|x| x.to_string()) {
+        let oneline_group = group.replace("\n", "");
+        let group_size = group.len() - oneline_group.len() + 1;
+
+        let mut map: HashMap<char, usize> = HashMap::new();
+        for c in oneline_group.chars() {
+            *map.entry(c).or_insert(0) += 1;
+        }
+
+        count += map.iter().filter(|(_, &v)| v == group_size).count() as u32;
+    }
+
+    count
+}
+
+pub fn check_input(input: &str) -> CheckResult {
+    let mut errors = input::check_lines(input, |line| {
+        match line.chars().find(|c| !c.is_ascii_lowercase()) {
+            Some(c) => Err(format!("unexpected character `{}`", c)),
+            None => Ok(()),
+        }
+    });
+
+    input::check_no_trailing_newline(input, &mut errors);
+
+    input::validated(errors, || {
+        Json::Array(
+            input
+                .split("\n\n")
+                .map(|group| group.lines().collect::<Vec<_>>().to_json())
+                .collect(),
+        )
+    })
+}
+
+pub const SOLUTION: Solution = Solution {
+    day: 6,
+    input_path: INPUT_PATH,
+    version: 1,
+    examples: &[EXAMPLE],
+    params: &[],
+    check_input,
+    part1: |input, _, _| part1(input).to_string(),
+    part2: Some(|input, _, _| part2(input).to_string()),
+    variants: &[],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault;
+
+    #[test]
+    fn day6_part1() {
+        assert_eq!(part1(EXAMPLE), 11);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day6_part1_input() {
+        assert_eq!(part1(&vault::read(INPUT_PATH).unwrap()), 6534);
+    }
+
+    #[test]
+    fn day6_part2() {
+        assert_eq!(part2(EXAMPLE), 6);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day6_part2_input() {
+        assert_eq!(part2(&vault::read(INPUT_PATH).unwrap()), 3402);
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+
+use crate::input::{self, CheckResult};
+use crate::json::{Json, ToJson};
+use crate::params::Param;
+use crate::solution::Solution;
+use crate::stats;
+
+pub const INPUT_PATH: &str = "src/day7/input.txt";
+pub const BAGNAME: &str = "shiny gold";
+
+// From the puzzle statement
+const EXAMPLE1: &str = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.
+bright aqua bags contain 5 plaid magenta bags, 5 muted lavender bags, 4 dim turquoise bags, 1 shiny turquoise bag.
+striped lavender bags contain 4 striped gold bags, 3 mirrored olive bags, 2 dim lime bags, 1 muted indigo bag.";
+
+const EXAMPLE2: &str = "shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.";
+
+fn parse_subbags(input: &str) -> Option<(u32, &str)> {
+    if !input.ends_with("no other bags.") {
+        let count: u32 = input.chars().next().unwrap().to_digit(10).unwrap();
+
+        let input = if input.ends_with('.') {
+            &input[0..(input.len() - 1)]
+        } else {
+            input
+        };
+
+        if input.ends_with("bags") {
+            Some((count, &input[2..(input.len() - 5)]))
+        } else {
+            Some((count, &input[2..(input.len() - 4)]))
+        }
+    } else {
+        None
+    }
+}
+
+fn parse_desc(input: &str) -> (&str, Vec<(u32, &str)>) {
+    let split: Vec<&str> = input.split(" contain ").collect();
+
+    let name = &split[0][0..(split[0].len() - 5)];
+
+    let content = split[1]
+        .split(", ")
+        .filter_map(|x| parse_subbags(x))
+        .collect();
+
+    (name, content)
+}
+
+fn parse_input(input: &str) -> HashMap<&str, Vec<(u32, &str)>> {
+    input.lines().map(|x| parse_desc(x)).collect()
+}
+
+fn find_bag(map: &HashMap<&str, Vec<(u32, &str)>>, name: &str, needle: &str) -> bool {
+    stats::count!("find_bag calls");
+
+    if let Some(content) = map.get(name) {
+        content
+            .iter()
+            .any(|(_, content)| *content == needle || find_bag(map, content, needle))
+    } else {
+        false
+    }
+}
+
+fn get_bag_content(map: &HashMap<&str, Vec<(u32, &str)>>, name: &str) -> u32 {
+    stats::count!("get_bag_content calls");
+
+    // A bag that is never described holds nothing, as `find_bag` assumes
+    map.get(name).map_or(0, |content| {
+        content.iter().fold(0, |acc, (count, name)| {
+            acc + (1 + get_bag_content(map, name)) * count
+        })
+    })
+}
+
+pub fn part1(input: &str, mybag: &str) -> u32 {
+    let map = parse_input(input);
+
+    map.iter()
+        .filter(|(&name, _)| name != mybag)
+        .filter(|(&name, _)| find_bag(&map, name, mybag))
+        .count() as u32
+}
+
+pub fn part2(input: &str, mybag: &str) -> u32 {
+    let map = parse_input(input);
+
+    get_bag_content(&map, mybag)
+}
+
+pub fn check_input(input: &str) -> CheckResult {
+    let errors = input::check_lines(input, |line| {
+        let (_, content) = line
+            .split_once(" bags contain ")
+            .ok_or("expected `<color> bags contain <content>.`")?;
+        let content = content.strip_suffix('.').ok_or("missing final `.`")?;
+
+        if content == "no other bags" {
+            return Ok(());
+        }
+
+        for subbag in content.split(", ") {
+            let (count, name) = subbag
+                .split_once(' ')
+                .ok_or_else(|| format!("expected `<count> <color> bags`, got `{}`", subbag))?;
+
+            // Counts are read as a single digit
+            if count.len() != 1 || !count.chars().all(|c| c.is_ascii_digit()) {
+                return Err(format!("invalid bag count `{}`", count));
+            }
+
+            // Bags that are never described are fine, like the ones of the puzzle statement
+            name.strip_suffix(" bags")
+                .or_else(|| name.strip_suffix(" bag"))
+                .ok_or_else(|| format!("expected `<count> <color> bags`, got `{}`", subbag))?;
+        }
+
+        Ok(())
+    });
+
+    input::validated(errors, || {
+        parse_input(input)
+            .into_iter()
+            .map(|(name, content)| {
+                let content = content
+                    .iter()
+                    .map(|(count, name)| {
+                        Json::object([("count", count.to_json()), ("name", name.to_json())])
+                    })
+                    .collect();
+
+                (name, Json::Array(content))
+            })
+            .collect::<HashMap<_, _>>()
+            .to_json()
+    })
+}
+
+pub const SOLUTION: Solution = Solution {
+    day: 7,
+    input_path: INPUT_PATH,
+    version: 1,
+    examples: &[EXAMPLE1, EXAMPLE2],
+    params: &[Param {
+        name: "bag",
+        default: BAGNAME,
+        help: "color of the bag to look for",
+        valid: |bag| !bag.is_empty(),
+        fuzz: None,
+    }],
+    check_input,
+    part1: |input, params, _| part1(input, params.value("bag")).to_string(),
+    part2: Some(|input, params, _| part2(input, params.value("bag")).to_string()),
+    variants: &[],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault;
+
+    #[test]
+    fn day7_part1() {
+        assert_eq!(part1(EXAMPLE1, BAGNAME), 4);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day7_part1_input() {
+        assert_eq!(part1(&vault::read(INPUT_PATH).unwrap(), BAGNAME), 248);
+    }
+
+    #[test]
+    fn day7_part2() {
+        assert_eq!(part2(EXAMPLE1, BAGNAME), 32);
+        assert_eq!(part2(EXAMPLE2, BAGNAME), 126);
+
+        // Undescribed bags, the looked for one included, are empty
+        assert_eq!(part2(EXAMPLE1, "no such"), 0);
+        assert_eq!(
+            part2("light red bags contain 2 dull red bags.", "light red"),
+            2
+        );
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day7_part2_input() {
+        assert_eq!(part2(&vault::read(INPUT_PATH).unwrap(), BAGNAME), 57281);
+    }
+
+    #[test]
+    fn day7_check_input() {
+        assert!(check_input(EXAMPLE1).is_ok());
+        assert!(check_input(EXAMPLE2).is_ok());
+
+        let errors = check_input(
+            "light red bags contain 1 dull red.
+bright white bags contain 12 shiny gold bags.
+shiny gold bags contain no other bags",
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            errors.iter().map(|e| e.line).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day7_check_puzzle_input() {
+        assert!(check_input(&vault::read(INPUT_PATH).unwrap()).is_ok());
+    }
+}
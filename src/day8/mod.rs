@@ -0,0 +1,386 @@
+use std::collections::HashSet;
+
+use crate::difftest;
+use crate::explain;
+use crate::input::{self, CheckResult};
+use crate::json::{Json, ToJson};
+use crate::log;
+use crate::property::{self, Property, PropertyTest, Rng};
+use crate::solution::{Solution, Variant};
+
+pub const INPUT_KO_PATH: &str = "src/day8/input_ko.txt";
+pub const INPUT_OK_PATH: &str = "src/day8/input_ok.txt";
+
+// From the puzzle statement
+const EXAMPLE1: &str = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+const EXAMPLE2: &str = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+nop -4
+acc +6";
+
+fn parse_code(input: &str) -> Vec<(&str, i32)> {
+    input
+        .lines()
+        .map(|l| {
+            let s: Vec<&str> = l.split(' ').collect();
+            (s[0], s[1].parse().unwrap())
+        })
+        .collect()
+}
+
+fn run(code: Vec<(&str, i32)>) -> (i32, bool) {
+    let mut accumulator: i32 = 0;
+    let mut pc: usize = 0;
+
+    let mut known_pc: HashSet<usize> = HashSet::new();
+
+    loop {
+        if known_pc.contains(&pc) {
+            log::debug!("loop detected, pc={}", pc);
+            return (accumulator, true);
+        } else if pc == code.len() {
+            return (accumulator, false);
+        } else {
+            known_pc.insert(pc);
+        }
+
+        let (opcode, data) = code[pc];
+
+        match opcode {
+            "acc" => {
+                log::trace!("{:3} - {} {:+4}        A:{}", pc, opcode, data, accumulator);
+                accumulator += data;
+                pc += 1;
+            }
+            "jmp" => {
+                let target_pc = if data > 0 {
+                    pc + data as usize
+                } else {
+                    pc - data.unsigned_abs() as usize
+                };
+
+                log::trace!(
+                    "{:3} - {} {:+4} [{:3}]  A:{}",
+                    pc,
+                    opcode,
+                    data,
+                    target_pc,
+                    accumulator
+                );
+
+                pc = target_pc;
+            }
+            "nop" => {
+                log::trace!("{:3} - {} {:+4}        A:{}", pc, opcode, data, accumulator);
+                pc += 1;
+            }
+            _ => {
+                panic!("Unknown opcode {}", opcode);
+            }
+        }
+    }
+}
+
+pub fn part1(input: &str) -> i32 {
+    let code = parse_code(input);
+
+    let (accumulator, _) = run(code);
+    accumulator
+}
+
+pub fn part2(input: &str) -> i32 {
+    let code = parse_code(input);
+
+    for (pc, new_opcode) in code.iter().enumerate().filter_map(|(pc, (opcode, _))| {
+        if *opcode == "jmp" {
+            Some((pc, "nop"))
+        } else if *opcode == "nop" {
+            Some((pc, "jmp"))
+        } else {
+            None
+        }
+    }) {
+        let mut patched_code = code.clone();
+        patched_code[pc].0 = new_opcode;
+
+        let (accumulator, loop_detected) = run(patched_code);
+        if !loop_detected {
+            explain::note("patch", || {
+                Json::object([
+                    ("pc", pc.to_json()),
+                    ("opcode", code[pc].0.to_json()),
+                    ("data", code[pc].1.to_json()),
+                    ("patched_opcode", new_opcode.to_json()),
+                ])
+            });
+
+            return accumulator;
+        }
+    }
+
+    0
+}
+
+fn jump_target(pc: usize, opcode: &str, data: i32) -> Option<usize> {
+    if opcode == "jmp" {
+        usize::try_from(pc as i32 + data).ok()
+    } else {
+        Some(pc + 1)
+    }
+}
+
+pub fn part2_graph(input: &str) -> i32 {
+    let code = parse_code(input);
+    let end = code.len();
+
+    // Walk the control flow graph backwards from the end of the program to find every
+    // instruction that terminates when the code is left untouched.
+    let mut predecessors: Vec<Vec<usize>> = vec![vec![]; end + 1];
+    for (pc, &(opcode, data)) in code.iter().enumerate() {
+        if let Some(target) = jump_target(pc, opcode, data).filter(|&t| t <= end) {
+            predecessors[target].push(pc);
+        }
+    }
+
+    let mut terminating = vec![false; end + 1];
+    let mut stack = vec![end];
+    terminating[end] = true;
+
+    while let Some(pc) = stack.pop() {
+        for &prev in &predecessors[pc] {
+            if !terminating[prev] {
+                terminating[prev] = true;
+                stack.push(prev);
+            }
+        }
+    }
+
+    // Only patching an instruction of the loop can change the execution. The patch is
+    // valid if it sends the program to a terminating instruction.
+    let mut visited = vec![false; end];
+    let mut candidates = vec![];
+    let mut pc = 0;
+
+    while pc < end && !visited[pc] {
+        visited[pc] = true;
+
+        let (opcode, data) = code[pc];
+        let patched_opcode = match opcode {
+            "jmp" => "nop",
+            "nop" => "jmp",
+            _ => opcode,
+        };
+
+        if patched_opcode != opcode
+            && jump_target(pc, patched_opcode, data).is_some_and(|t| t <= end && terminating[t])
+        {
+            candidates.push(pc);
+        }
+
+        pc = jump_target(pc, opcode, data).unwrap();
+    }
+
+    // Several patches may work, keep the first one like the brute force does
+    match candidates.iter().min() {
+        Some(&pc) => {
+            let mut patched_code = code.clone();
+            patched_code[pc].0 = if code[pc].0 == "jmp" { "nop" } else { "jmp" };
+
+            run(patched_code).0
+        }
+        None => 0,
+    }
+}
+
+const OPCODES: [&str; 3] = ["nop", "acc", "jmp"];
+
+fn render(code: &[(u8, i32)]) -> String {
+    code.iter()
+        .map(|&(opcode, data)| format!("{} {:+}", OPCODES[opcode as usize], data))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn generate(rng: &mut Rng) -> Vec<(u8, i32)> {
+    let len = 1 + rng.below(20) as i64;
+
+    (0..len)
+        .map(|pc| {
+            let opcode = rng.below(OPCODES.len() as u64) as u8;
+            let data = if OPCODES[opcode as usize] == "acc" {
+                rng.range(-5..=5)
+            } else {
+                rng.range(0..=len) - pc
+            };
+
+            (opcode, data as i32)
+        })
+        .collect()
+}
+
+// Jumps must stay within the program, even once patched, and the unpatched program
+// must loop for the puzzle to make sense.
+fn is_valid_program(code: &[(u8, i32)]) -> bool {
+    let in_bounds = code.iter().enumerate().all(|(pc, &(opcode, data))| {
+        OPCODES[opcode as usize] == "acc"
+            || jump_target(pc, "jmp", data).is_some_and(|t| t <= code.len())
+    });
+
+    in_bounds && run(parse_code(&render(code))).1
+}
+
+pub fn difftests() -> Vec<Box<dyn PropertyTest>> {
+    vec![Box::new(Property {
+        name: "day8 part2",
+        generate,
+        precondition: |code| is_valid_program(code),
+        holds: |code| {
+            let input = render(code);
+            difftest::agree(part2(&input), part2_graph(&input))
+        },
+    })]
+}
+
+pub fn properties() -> Vec<Box<dyn PropertyTest>> {
+    vec![Box::new(Property {
+        name: "day8 program round trip",
+        generate: |rng| {
+            (0..rng.below(20))
+                .map(|_| {
+                    let opcode = rng.below(OPCODES.len() as u64) as u8;
+                    (opcode, rng.range(i32::MIN as i64..=i32::MAX as i64) as i32)
+                })
+                .collect()
+        },
+        precondition: |_| true,
+        holds: |code: &Vec<(u8, i32)>| {
+            let parsed: Vec<_> = parse_code(&render(code))
+                .into_iter()
+                .map(|(opcode, data)| {
+                    let opcode = OPCODES.iter().position(|&o| o == opcode).unwrap();
+                    (opcode as u8, data)
+                })
+                .collect();
+
+            property::expect_eq(code, &parsed)
+        },
+    })]
+}
+
+pub fn check_input(input: &str) -> CheckResult {
+    let end = input.lines().count();
+    let mut pc = 0;
+
+    let errors = input::check_lines(input, |line| {
+        let current_pc = pc;
+        pc += 1;
+
+        let (opcode, data) = line
+            .split_once(' ')
+            .ok_or("expected `<opcode> <argument>`")?;
+        let data: i32 = input::parse(data)?;
+
+        match opcode {
+            "acc" | "nop" => Ok(()),
+            "jmp" => match jump_target(current_pc, opcode, data) {
+                Some(target) if target <= end => Ok(()),
+                _ => Err(format!(
+                    "jump to {} is outside the program",
+                    current_pc as i32 + data
+                )),
+            },
+            _ => Err(format!("unknown opcode `{}`", opcode)),
+        }
+    });
+
+    input::validated(errors, || {
+        Json::Array(
+            parse_code(input)
+                .iter()
+                .map(|(opcode, data)| {
+                    Json::object([("opcode", opcode.to_json()), ("data", data.to_json())])
+                })
+                .collect(),
+        )
+    })
+}
+
+pub const SOLUTION: Solution = Solution {
+    day: 8,
+    input_path: INPUT_KO_PATH,
+    version: 1,
+    examples: &[EXAMPLE1, EXAMPLE2],
+    params: &[],
+    check_input,
+    part1: |input, _, _| part1(input).to_string(),
+    part2: Some(|input, _, _| part2(input).to_string()),
+    variants: &[Variant {
+        name: "graph",
+        part1: None,
+        part2: Some(|input, _, _| part2_graph(input).to_string()),
+    }],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault;
+
+    #[test]
+    fn day8_part1() {
+        assert_eq!(run(parse_code(EXAMPLE1)), (5, true));
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day8_part1_input() {
+        assert_eq!(
+            run(parse_code(&vault::read(INPUT_KO_PATH).unwrap())),
+            (1941, true)
+        );
+    }
+
+    #[test]
+    fn day8_part2() {
+        assert_eq!(run(parse_code(EXAMPLE2)), (8, false));
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day8_part2_input() {
+        assert_eq!(
+            run(parse_code(&vault::read(INPUT_OK_PATH).unwrap())),
+            (2096, false)
+        );
+        assert_eq!(part2_graph(&vault::read(INPUT_KO_PATH).unwrap()), 2096);
+    }
+
+    #[test]
+    fn day8_check_input() {
+        let errors = check_input("nop +0\njmp +9\nxyz +1\nacc q").unwrap_err();
+        assert_eq!(
+            errors.iter().map(|e| e.line).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day8_check_puzzle_input() {
+        assert!(check_input(&vault::read(INPUT_KO_PATH).unwrap()).is_ok());
+    }
+}
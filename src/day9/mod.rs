@@ -0,0 +1,164 @@
+use crate::explain;
+use crate::input::{self, CheckResult};
+use crate::json::{Json, ToJson};
+use crate::params::Param;
+use crate::solution::Solution;
+
+pub const INPUT_PATH: &str = "src/day9/input.txt";
+pub const WINDOW: usize = 25;
+
+// From the puzzle statement
+const EXAMPLE: &str = "35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576";
+
+fn parse_input(input: &str) -> Vec<u64> {
+    input.lines().map(|x| x.parse().unwrap()).collect()
+}
+
+fn find_needle(values: &[u64], needle: u64) -> bool {
+    let mut values: Vec<_> = values.iter().filter(|&&x| x < needle).copied().collect();
+    values.sort_unstable();
+
+    // Pairs of distinct entries, the last one included
+    (0..values.len()).any(|idx| values[idx + 1..].iter().any(|&x| x + values[idx] == needle))
+}
+
+pub fn part1(input: &str, window_size: usize) -> u64 {
+    let values = parse_input(input);
+
+    for i in window_size..values.len() {
+        let prev_values = &values[i - window_size..i];
+        let needle = values[i];
+
+        if !find_needle(prev_values, needle) {
+            explain::note("invalid_number", || {
+                Json::object([("index", i.to_json()), ("value", needle.to_json())])
+            });
+            return needle;
+        }
+    }
+
+    0
+}
+
+pub fn part2(input: &str, window_size: usize) -> u64 {
+    let values = parse_input(input);
+    let needle = part1(input, window_size);
+
+    for i in 0..values.len() {
+        let mut sum = 0;
+
+        for j in i..values.len() {
+            sum += values[j];
+
+            if sum == needle {
+                let range = &values[i..(j + 1)];
+                explain::note("range", || {
+                    Json::object([
+                        ("start", i.to_json()),
+                        ("end", j.to_json()),
+                        ("min", range.iter().min().to_json()),
+                        ("max", range.iter().max().to_json()),
+                    ])
+                });
+                return range.iter().min().unwrap() + range.iter().max().unwrap();
+            } else if sum > needle {
+                break;
+            }
+        }
+    }
+
+    0
+}
+
+pub fn check_input(input: &str) -> CheckResult {
+    let errors = input::check_lines(input, |line| input::parse::<u64>(line).map(|_| ()));
+
+    input::validated(errors, || parse_input(input).to_json())
+}
+
+pub const SOLUTION: Solution = Solution {
+    day: 9,
+    input_path: INPUT_PATH,
+    version: 2,
+    examples: &[EXAMPLE],
+    params: &[Param {
+        name: "window",
+        default: "25",
+        // A shorter preamble holds no pair, every number after it would be invalid
+        help: "length of the preamble, at least 2",
+        valid: |window| window.parse::<usize>().is_ok_and(|window| window >= 2),
+        fuzz: None,
+    }],
+    check_input,
+    part1: |input, params, _| part1(input, params.get("window")).to_string(),
+    part2: Some(|input, params, _| part2(input, params.get("window")).to_string()),
+    variants: &[],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::Params;
+    use crate::vault;
+
+    #[test]
+    fn day9_part1() {
+        assert_eq!(part1(EXAMPLE, 5), 127);
+        // 5 is the sum of the last two entries of its preamble
+        assert_eq!(part1("1\n2\n3\n5\n9", 3), 9);
+        assert_eq!(part1(EXAMPLE, EXAMPLE.lines().count()), 0);
+        // The smallest preamble, a single pair
+        assert_eq!(part1("1\n2\n3\n4", 2), 4);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day9_part1_input() {
+        assert_eq!(part1(&vault::read(INPUT_PATH).unwrap(), WINDOW), 1212510616);
+    }
+
+    #[test]
+    fn day9_window() {
+        let window = |value: &str| Params::new(SOLUTION.params, &[format!("window={}", value)]);
+
+        assert!(window("2").is_ok());
+        assert_eq!(
+            window("1"),
+            Err(
+                "invalid value `1` for parameter `window` (length of the preamble, at least 2)"
+                    .to_string()
+            )
+        );
+        assert!(window("0").is_err());
+    }
+
+    #[test]
+    fn day9_part2() {
+        assert_eq!(part2(EXAMPLE, 5), 62);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn day9_part2_input() {
+        assert_eq!(part2(&vault::read(INPUT_PATH).unwrap(), WINDOW), 171265123);
+    }
+}
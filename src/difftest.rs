@@ -0,0 +1,70 @@
+//! Differential testing of reference solvers against optimized ones.
+//!
+//! A difftest is a [`Property`](crate::property::Property) whose invariant is that an obvious
+//! implementation and an optimized one [`agree`], so that it is generated, checked and shrunk
+//! like any other property.
+
+use std::fmt::Debug;
+
+use crate::property::PropertyTest;
+use crate::{day1, day10, day15, day8};
+
+pub fn agree<O: PartialEq + Debug>(reference: O, optimized: O) -> Result<(), String> {
+    if reference == optimized {
+        Ok(())
+    } else {
+        Err(format!(
+            "reference gives {:?}, optimized gives {:?}",
+            reference, optimized
+        ))
+    }
+}
+
+pub fn registry() -> Vec<Box<dyn PropertyTest>> {
+    let mut pairs = vec![];
+
+    pairs.extend(day1::difftests());
+    pairs.extend(day8::difftests());
+    pairs.extend(day10::difftests());
+    pairs.extend(day15::difftests());
+
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property::{self, Property};
+
+    const CASES: usize = 300;
+
+    #[test]
+    fn difftest_registry() {
+        let seed = property::seed();
+
+        for pair in registry() {
+            if let Err(failure) = pair.run(seed, CASES) {
+                panic!("{}", failure);
+            }
+        }
+    }
+
+    #[test]
+    fn difftest_shrink() {
+        let pair = Property {
+            name: "broken sum",
+            generate: |rng| (0..rng.below(10)).map(|_| rng.below(100) as u32).collect(),
+            precondition: |_| true,
+            holds: |v: &Vec<u32>| {
+                agree(
+                    v.iter().sum::<u32>(),
+                    v.iter().filter(|&&x| x <= 10).sum::<u32>(),
+                )
+            },
+        };
+
+        let failure = property::check(&pair, 42, 100).unwrap_err();
+        assert_eq!(failure.input, vec![11]);
+        assert_eq!(failure.message, "reference gives 11, optimized gives 0");
+    }
+}
@@ -0,0 +1,76 @@
+//! Structured channel for the intermediate results of the solvers.
+//!
+//! Solvers call [`note`] with the key steps leading to their answer. Notes are only
+//! recorded while running under [`capture`], so they cost nothing the rest of the time.
+
+use std::cell::RefCell;
+
+use crate::json::{Json, ToJson};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Note {
+    pub key: &'static str,
+    pub value: Json,
+}
+
+impl ToJson for Note {
+    fn to_json(&self) -> Json {
+        Json::object([("key", self.key.to_json()), ("value", self.value.clone())])
+    }
+}
+
+thread_local! {
+    static NOTES: RefCell<Option<Vec<Note>>> = const { RefCell::new(None) };
+}
+
+pub fn enabled() -> bool {
+    NOTES.with(|notes| notes.borrow().is_some())
+}
+
+// The value is built lazily, only when notes are being captured
+pub fn note(key: &'static str, value: impl FnOnce() -> Json) {
+    NOTES.with(|notes| {
+        if let Some(notes) = notes.borrow_mut().as_mut() {
+            notes.push(Note {
+                key,
+                value: value(),
+            });
+        }
+    });
+}
+
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<Note>) {
+    let previous = NOTES.with(|notes| notes.replace(Some(vec![])));
+    let result = f();
+    let notes = NOTES
+        .with(|notes| notes.replace(previous))
+        .unwrap_or_default();
+
+    (result, notes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explain_capture() {
+        note("ignored", || 0.to_json());
+
+        let (answer, notes) = capture(|| {
+            assert!(enabled());
+            note("step", || 1.to_json());
+            42
+        });
+
+        assert_eq!(answer, 42);
+        assert_eq!(
+            notes,
+            vec![Note {
+                key: "step",
+                value: Json::Int(1)
+            }]
+        );
+        assert!(!enabled());
+    }
+}
@@ -0,0 +1,176 @@
+//! Mutation fuzzing of the parsers and solvers.
+//!
+//! Inputs are derived from the examples and the real input by a few random edits. Panics
+//! are caught with [`catch`], and the inputs causing them are kept in a corpus directory.
+
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use crate::cache;
+use crate::property::Rng;
+
+pub const CORPUS_DIR: &str = "fuzz/corpus";
+
+// Characters meaningful to at least one of the parsers
+const SPECIAL_CHARS: &[char] = &[
+    '\n', ' ', '-', '+', ':', ',', '.', '#', 'x', '0', '1', '9', '[', ']', '=',
+];
+
+// Numbers at the edges of the integer types used by the parsers
+const SPECIAL_NUMBERS: &[&str] = &["0", "-1", "255", "4294967296", "99999999999999999999"];
+
+thread_local! {
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+fn pick<T: Copy>(rng: &mut Rng, items: &[T]) -> T {
+    items[rng.below(items.len() as u64) as usize]
+}
+
+fn mutate_once(rng: &mut Rng, chars: &mut Vec<char>) {
+    let pos = |rng: &mut Rng, len: usize| rng.below(len as u64 + 1) as usize;
+
+    match rng.below(8) {
+        0 if !chars.is_empty() => {
+            chars.remove(pos(rng, chars.len() - 1));
+        }
+        1 => {
+            let c = if chars.is_empty() || rng.below(2) == 0 {
+                pick(rng, SPECIAL_CHARS)
+            } else {
+                pick(rng, chars)
+            };
+            let at = pos(rng, chars.len());
+            chars.insert(at, c);
+        }
+        2 if !chars.is_empty() => {
+            let at = pos(rng, chars.len() - 1);
+            chars[at] = pick(rng, SPECIAL_CHARS);
+        }
+        3..=5 => {
+            let text: String = chars.iter().collect();
+            let mut lines: Vec<&str> = text.split('\n').collect();
+            let a = pos(rng, lines.len() - 1);
+            let b = pos(rng, lines.len() - 1);
+
+            match rng.below(3) {
+                0 => {
+                    lines.remove(a);
+                }
+                1 => lines.insert(a, lines[b]),
+                _ => lines.swap(a, b),
+            }
+
+            *chars = lines.join("\n").chars().collect();
+        }
+        6 => chars.truncate(pos(rng, chars.len())),
+        _ => {
+            // Replaces the number starting at a random digit
+            let digits: Vec<usize> = (0..chars.len())
+                .filter(|&i| chars[i].is_ascii_digit())
+                .collect();
+
+            if !digits.is_empty() {
+                let start = pick(rng, &digits);
+                let end = (start..chars.len())
+                    .find(|&i| !chars[i].is_ascii_digit())
+                    .unwrap_or(chars.len());
+
+                chars.splice(start..end, pick(rng, SPECIAL_NUMBERS).chars());
+            }
+        }
+    }
+}
+
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+
+    for _ in 0..1 + rng.below(3) {
+        mutate_once(rng, &mut chars);
+    }
+
+    chars.into_iter().collect()
+}
+
+// Replaces the default panic hook, which prints every caught panic, by one recording
+// where the panic happened for `catch`
+pub fn silence_panics() {
+    panic::set_hook(Box::new(|info| {
+        let location = info.location().map(|l| l.to_string());
+        LOCATION.with(|cell| *cell.borrow_mut() = location);
+    }));
+}
+
+// The error is the panic message, followed by its location when panics are silenced
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let message = if let Some(s) = payload.downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = payload.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "non-string panic payload".to_string()
+        };
+
+        match LOCATION.with(|cell| cell.borrow_mut().take()) {
+            Some(location) => format!("{} at {}", message, location),
+            None => message,
+        }
+    })
+}
+
+// Named by hash, so a crash found twice is stored once
+pub fn save(dir: &Path, day: u32, input: &str) -> io::Result<PathBuf> {
+    let dir = dir.join(format!("day{}", day));
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(format!("{:016x}.txt", cache::hash(input.as_bytes())));
+    fs::write(&path, input)?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn fuzz_mutate() {
+        let input = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc";
+        let mutations: Vec<_> = (0..20)
+            .map(|seed| mutate(&mut Rng::new(seed), input))
+            .collect();
+
+        assert_eq!(mutate(&mut Rng::new(3), input), mutations[3]);
+        assert!(mutations.iter().any(|m| m != input));
+        assert!(mutate(&mut Rng::new(0), "").len() <= 3);
+    }
+
+    #[test]
+    fn fuzz_catch() {
+        assert_eq!(catch(|| 42), Ok(42));
+
+        let v: Vec<u32> = vec![];
+        let error = catch(|| v[1]).unwrap_err();
+        assert!(error.contains("index out of bounds"), "{}", error);
+
+        let error = catch(|| panic!("value {}", 7)).unwrap_err();
+        assert!(error.starts_with("value 7"), "{}", error);
+    }
+
+    #[test]
+    fn fuzz_save() {
+        let dir = env::temp_dir().join(format!("aoc-fuzz-test-{}", std::process::id()));
+
+        let path = save(&dir, 2, "1-3 a").unwrap();
+        assert_eq!(save(&dir, 2, "1-3 a").unwrap(), path);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1-3 a");
+        assert_eq!(fs::read_dir(dir.join("day2")).unwrap().count(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,227 @@
+//! Local history of the answers given for each part, with the verdict they got.
+//!
+//! Each day and part has its own file of `verdict answer` lines, filled from the server's
+//! replies or by hand when working offline. The verdicts bound the answers worth giving.
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub const DEFAULT_DIR: &str = ".aoc-history";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    // Rejected without a hint
+    Wrong,
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            _ => Err(format!(
+                "unknown verdict `{}`, expected correct, too-high, too-low or wrong",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+        };
+
+        f.pad(name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Guess {
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+pub struct History {
+    path: PathBuf,
+    pub guesses: Vec<Guess>,
+}
+
+impl History {
+    pub fn load(dir: &Path, day: u32, part: u32) -> Result<Self, String> {
+        let path = dir.join(format!("day{}-part{}", day, part));
+
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("cannot read {}: {}", path.display(), e)),
+        };
+
+        let guesses = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| {
+                let error =
+                    |message: String| format!("{}:{}: {}", path.display(), idx + 1, message);
+                let (verdict, answer) = line
+                    .split_once(' ')
+                    .ok_or_else(|| error("expected `verdict answer`".to_string()))?;
+
+                Ok(Guess {
+                    answer: answer.trim().to_string(),
+                    verdict: verdict.parse().map_err(error)?,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Self { path, guesses })
+    }
+
+    pub fn record(&mut self, answer: &str, verdict: Verdict) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{} {}", verdict, answer)?;
+
+        self.guesses.push(Guess {
+            answer: answer.to_string(),
+            verdict,
+        });
+        Ok(())
+    }
+
+    pub fn accepted(&self) -> Option<&str> {
+        self.guesses
+            .iter()
+            .find(|guess| guess.verdict == Verdict::Correct)
+            .map(|guess| guess.answer.as_str())
+    }
+
+    // Greatest answer known too low and lowest answer known too high
+    pub fn bounds(&self) -> (Option<i64>, Option<i64>) {
+        let numbers = |verdict| {
+            self.guesses
+                .iter()
+                .filter(move |guess| guess.verdict == verdict)
+                .filter_map(|guess| guess.answer.parse::<i64>().ok())
+        };
+
+        (
+            numbers(Verdict::TooLow).max(),
+            numbers(Verdict::TooHigh).min(),
+        )
+    }
+
+    // Why the answer cannot be the right one, empty when the history allows it
+    pub fn warnings(&self, answer: &str) -> Vec<String> {
+        let mut warnings = vec![];
+
+        if let Some(accepted) = self.accepted() {
+            if accepted != answer {
+                warnings.push(format!("the accepted answer is {}", accepted));
+            }
+            return warnings;
+        }
+
+        if let Some(guess) = self.guesses.iter().find(|guess| guess.answer == answer) {
+            warnings.push(format!(
+                "{} was already rejected as {}",
+                answer, guess.verdict
+            ));
+            return warnings;
+        }
+
+        if let Ok(n) = answer.parse::<i64>() {
+            let (low, high) = self.bounds();
+
+            if let Some(low) = low.filter(|&low| n <= low) {
+                warnings.push(format!("{} is not above {}, known too low", answer, low));
+            }
+            if let Some(high) = high.filter(|&high| n >= high) {
+                warnings.push(format!("{} is not below {}, known too high", answer, high));
+            }
+        }
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn history_warnings() {
+        let dir = env::temp_dir().join(format!("aoc-history-test-{}", std::process::id()));
+        let mut history = History::load(&dir, 1, 2).unwrap();
+        assert_eq!(history.bounds(), (None, None));
+        assert!(history.warnings("500").is_empty());
+
+        history.record("500", Verdict::TooHigh).unwrap();
+        history.record("100", Verdict::TooLow).unwrap();
+        history.record("300", Verdict::Wrong).unwrap();
+        history.record("450", Verdict::TooHigh).unwrap();
+
+        let history = History::load(&dir, 1, 2).unwrap();
+        assert_eq!(history.guesses.len(), 4);
+        assert_eq!(history.bounds(), (Some(100), Some(450)));
+
+        assert!(history.warnings("200").is_empty());
+        assert_eq!(
+            history.warnings("300"),
+            ["300 was already rejected as wrong"]
+        );
+        assert_eq!(
+            history.warnings("100"),
+            ["100 was already rejected as too-low"]
+        );
+        assert_eq!(
+            history.warnings("460"),
+            ["460 is not below 450, known too high"]
+        );
+        assert_eq!(
+            history.warnings("50"),
+            ["50 is not above 100, known too low"]
+        );
+
+        let mut history = history;
+        history.record("200", Verdict::Correct).unwrap();
+        assert!(history.warnings("200").is_empty());
+        assert_eq!(history.warnings("201"), ["the accepted answer is 200"]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn history_malformed() {
+        let dir = env::temp_dir().join(format!("aoc-history-malformed-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day3-part1"), "too-high 10\nmaybe 5\n").unwrap();
+
+        let error = History::load(&dir, 3, 1).err().unwrap();
+        assert!(error.ends_with("day3-part1:2: unknown verdict `maybe`, expected correct, too-high, too-low or wrong"), "{}", error);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
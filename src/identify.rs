@@ -0,0 +1,315 @@
+//! Guesses which day an input file belongs to.
+//!
+//! Every day's parser is run on the file, then the [`Shape`] of the file is compared with
+//! the one of the day's own input: blank-line records, grid width, characters, size and
+//! range of the numbers. Both scores make the confidence of each day.
+
+use std::collections::BTreeSet;
+
+use crate::fuzz;
+use crate::solution::Solution;
+
+// Share of the parser in the confidence, the shape making the rest
+const PARSE_WEIGHT: f64 = 0.6;
+
+// Features at least this similar are reported as matching
+const MATCH_THRESHOLD: f64 = 0.8;
+
+#[derive(Debug, PartialEq)]
+pub struct Shape {
+    pub lines: usize,
+    // Groups of lines separated by blank lines
+    pub records: usize,
+    // Width of the lines when they all have the same one
+    pub grid_width: Option<usize>,
+    pub chars: BTreeSet<char>,
+    pub numbers: Option<(i64, i64)>,
+}
+
+impl Shape {
+    pub fn of(input: &str) -> Self {
+        let lines: Vec<&str> = input.lines().collect();
+        let filled: Vec<&str> = lines.iter().copied().filter(|l| !l.is_empty()).collect();
+
+        let records = input
+            .split("\n\n")
+            .filter(|record| !record.trim().is_empty())
+            .count();
+
+        let grid_width = match filled.first() {
+            Some(first) if filled.len() > 1 && filled.iter().all(|l| l.len() == first.len()) => {
+                Some(first.len())
+            }
+            _ => None,
+        };
+
+        let numbers = numbers(input).fold(None, |range, n| match range {
+            None => Some((n, n)),
+            Some((min, max)) => Some((n.min(min), n.max(max))),
+        });
+
+        Self {
+            lines: lines.len(),
+            records,
+            grid_width,
+            chars: input.chars().filter(|c| !c.is_whitespace()).collect(),
+            numbers,
+        }
+    }
+
+    // Each feature of `other` with its similarity, from 0 to 1
+    pub fn compare(&self, other: &Shape) -> Vec<(String, f64)> {
+        let same = |same| if same { 1.0 } else { 0.0 };
+
+        let grid = match (self.grid_width, other.grid_width) {
+            (Some(a), Some(b)) => same(a == b) * 0.5 + 0.5,
+            (a, b) => same(a.is_none() && b.is_none()),
+        };
+
+        let union = self.chars.union(&other.chars).count();
+        let chars = match union {
+            0 => 1.0,
+            _ => self.chars.intersection(&other.chars).count() as f64 / union as f64,
+        };
+
+        let numbers = match (self.numbers, other.numbers) {
+            (Some(a), Some(b)) => range_overlap(a, b),
+            (a, b) => same(a.is_none() && b.is_none()),
+        };
+
+        let size = match self.lines.max(other.lines) {
+            0 => 1.0,
+            max => self.lines.min(other.lines) as f64 / max as f64,
+        };
+
+        let records = match other.records {
+            0 | 1 => "one record per line".to_string(),
+            _ => "blank-line records".to_string(),
+        };
+
+        vec![
+            (records, same((self.records > 1) == (other.records > 1))),
+            (
+                other
+                    .grid_width
+                    .map_or("uneven lines".to_string(), |w| format!("grid {} wide", w)),
+                grid,
+            ),
+            (format!("{} characters", other.chars.len()), chars),
+            (
+                other
+                    .numbers
+                    .map_or("no numbers".to_string(), |(min, max)| {
+                        format!("numbers {}..{}", min, max)
+                    }),
+                numbers,
+            ),
+            (format!("{} line(s)", other.lines), size),
+        ]
+    }
+}
+
+// Runs of digits, with their sign unless the `-` follows a digit as in `1-3`
+fn numbers(input: &str) -> impl Iterator<Item = i64> + '_ {
+    let bytes = input.as_bytes();
+
+    input
+        .match_indices(|c: char| c.is_ascii_digit())
+        .filter(move |&(start, _)| start == 0 || !bytes[start - 1].is_ascii_digit())
+        .filter_map(move |(start, _)| {
+            let end = input[start..]
+                .find(|c: char| !c.is_ascii_digit())
+                .map_or(input.len(), |len| start + len);
+            let negative =
+                start >= 2 && bytes[start - 1] == b'-' && !bytes[start - 2].is_ascii_digit()
+                    || start == 1 && bytes[0] == b'-';
+            let n: i64 = input[start..end].parse().ok()?;
+            Some(if negative { -n } else { n })
+        })
+}
+
+// Overlap of two ranges on a logarithmic scale, so that 1-2000 and 1-3000 are close but
+// 1-2000 and 1-2^36 are not
+fn range_overlap(a: (i64, i64), b: (i64, i64)) -> f64 {
+    let log = |n: i64| (n.unsigned_abs() as f64).ln_1p().copysign(n as f64);
+    let (a, b) = ((log(a.0), log(a.1)), (log(b.0), log(b.1)));
+
+    let union = a.1.max(b.1) - a.0.min(b.0);
+    if union == 0.0 {
+        return 1.0;
+    }
+
+    (a.1.min(b.1) - a.0.max(b.0)).max(0.0) / union
+}
+
+pub struct Candidate {
+    pub day: u32,
+    // Malformed lines reported by the parser, or its panic
+    pub parse: Result<usize, String>,
+    // Features matching the day's own input, when there is one
+    pub matching: Option<Vec<String>>,
+    pub confidence: f64,
+}
+
+impl Candidate {
+    pub fn accepted(&self) -> bool {
+        self.parse == Ok(0)
+    }
+}
+
+// Days accepting the input first, each group by decreasing confidence, `reference` giving
+// the day's own input if any
+pub fn rank(
+    input: &str,
+    solutions: &[&Solution],
+    reference: impl Fn(&Solution) -> Option<String>,
+) -> Vec<Candidate> {
+    let shape = Shape::of(input);
+    let lines = input.lines().count().max(1);
+
+    let mut candidates: Vec<_> = solutions
+        .iter()
+        .map(|solution| {
+            let parse = fuzz::catch(|| (solution.check_input)(input))
+                .map(|checked| checked.err().map_or(0, |errors| errors.len()));
+
+            // A rejected input still beats one the parser does not make sense of
+            let parse_score = match parse {
+                Ok(0) => 1.0,
+                Ok(errors) => 0.5 * lines.saturating_sub(errors) as f64 / lines as f64,
+                Err(_) => 0.0,
+            };
+
+            let similarities = reference(solution).map(|own| shape.compare(&Shape::of(&own)));
+
+            let confidence = match &similarities {
+                Some(similarities) => {
+                    let total: f64 = similarities.iter().map(|(_, s)| s).sum();
+                    PARSE_WEIGHT * parse_score
+                        + (1.0 - PARSE_WEIGHT) * total / similarities.len() as f64
+                }
+                None => parse_score,
+            };
+
+            Candidate {
+                day: solution.day,
+                parse,
+                matching: similarities.map(|similarities| {
+                    similarities
+                        .into_iter()
+                        .filter(|&(_, s)| s >= MATCH_THRESHOLD)
+                        .map(|(name, _)| name)
+                        .collect()
+                }),
+                confidence,
+            }
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| {
+        b.accepted()
+            .cmp(&a.accepted())
+            .then(b.confidence.total_cmp(&a.confidence))
+            .then(a.day.cmp(&b.day))
+    });
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day1;
+    use crate::input;
+    use crate::json::Json;
+    use crate::solution::SOLUTIONS;
+    use crate::vault;
+
+    #[test]
+    fn identify_shape() {
+        let shape = Shape::of("L.L\n#L#\n\nLL.\n");
+        assert_eq!(shape.lines, 4);
+        assert_eq!(shape.records, 2);
+        assert_eq!(shape.grid_width, Some(3));
+        assert_eq!(shape.chars, ['#', '.', 'L'].into_iter().collect());
+        assert_eq!(shape.numbers, None);
+
+        let shape = Shape::of("nop +0\nacc -99\njmp +4");
+        assert_eq!(shape.grid_width, None);
+        assert_eq!(shape.numbers, Some((-99, 4)));
+        assert_eq!(
+            numbers("-2 1-3 a: x-5,7").collect::<Vec<_>>(),
+            [-2, 1, 3, -5, 7]
+        );
+
+        assert_eq!(range_overlap((1, 2000), (1, 2000)), 1.0);
+        assert_eq!(range_overlap((1, 10), (100, 1000)), 0.0);
+        assert!(range_overlap((1, 2000), (1, 3000)) > 0.9);
+    }
+
+    #[test]
+    fn identify_accepted_first() {
+        let input = "1\n2\n3\n4\n5\n6\n7\n8\n9\nx";
+        let accepting = Solution {
+            day: 1,
+            check_input: |_| Ok(Json::Null),
+            ..day1::SOLUTION
+        };
+        // Rejects a single line, but its own input looks just the same
+        let picky = Solution {
+            day: 2,
+            check_input: |input| {
+                let errors = input::check_lines(input, |line| match line {
+                    "x" => Err("not a number".to_string()),
+                    _ => Ok(()),
+                });
+                input::validated(errors, || Json::Null)
+            },
+            ..day1::SOLUTION
+        };
+
+        let candidates = rank(input, &[&picky, &accepting], |solution| {
+            match solution.day {
+                1 => Some("#.#\n\n..#".to_string()),
+                _ => Some(input.to_string()),
+            }
+        });
+
+        assert!(candidates[1].confidence > candidates[0].confidence);
+        assert_eq!(candidates[0].day, 1);
+        assert!(candidates[0].accepted());
+        assert_eq!(candidates[1].parse, Ok(1));
+    }
+
+    // Every other line, or record, of an input stands for the input of another player
+    fn sample(input: &str) -> Option<String> {
+        let shape = Shape::of(input);
+        let (separator, parts): (_, Vec<&str>) = match shape.records {
+            1 => ("\n", input.lines().collect()),
+            _ => ("\n\n", input.split("\n\n").collect()),
+        };
+
+        (parts.len() > 3).then(|| {
+            parts
+                .iter()
+                .step_by(2)
+                .copied()
+                .collect::<Vec<_>>()
+                .join(separator)
+        })
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn identify_rank() {
+        let own = |solution: &Solution| vault::read(solution.input_path).ok();
+
+        for solution in SOLUTIONS {
+            let Some(input) = own(solution).as_deref().and_then(sample) else {
+                continue;
+            };
+            let candidates = rank(&input, &SOLUTIONS, own);
+
+            assert_eq!(candidates[0].day, solution.day);
+        }
+    }
+}
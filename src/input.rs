@@ -0,0 +1,96 @@
+//! Input validation shared by the days' `check_input` functions.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::json::Json;
+
+#[derive(Debug, PartialEq)]
+pub struct InputError {
+    pub line: usize,
+    pub content: String,
+    pub message: String,
+}
+
+impl InputError {
+    // Lines are numbered from 1, like in an editor
+    pub fn new(line: usize, content: &str, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            content: content.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}: {} ({:?})",
+            self.line, self.message, self.content
+        )
+    }
+}
+
+// Parsed structure on success, every malformed line otherwise
+pub type CheckResult = Result<Json, Vec<InputError>>;
+
+// Runs `check` on every line and collects all the errors instead of stopping at the first one
+pub fn check_lines<F>(input: &str, mut check: F) -> Vec<InputError>
+where
+    F: FnMut(&str) -> Result<(), String>,
+{
+    if input.is_empty() {
+        return vec![InputError::new(1, "", "empty input")];
+    }
+
+    input
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            check(line)
+                .err()
+                .map(|message| InputError::new(idx + 1, line, message))
+        })
+        .collect()
+}
+
+// Solvers splitting records on blank lines choke on a final newline
+pub fn check_no_trailing_newline(input: &str, errors: &mut Vec<InputError>) {
+    if input.ends_with('\n') {
+        let line = input.lines().count() + 1;
+        errors.push(InputError::new(line, "", "unexpected trailing newline"));
+    }
+}
+
+pub fn parse<T: FromStr>(s: &str) -> Result<T, String> {
+    s.parse().map_err(|_| format!("invalid number `{}`", s))
+}
+
+pub fn validated(errors: Vec<InputError>, dump: impl FnOnce() -> Json) -> CheckResult {
+    if errors.is_empty() {
+        Ok(dump())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_check_lines() {
+        let errors = check_lines("1\nx\n3\ny", |line| parse::<u32>(line).map(|_| ()));
+
+        assert_eq!(
+            errors,
+            vec![
+                InputError::new(2, "x", "invalid number `x`"),
+                InputError::new(4, "y", "invalid number `y`"),
+            ]
+        );
+        assert_eq!(check_lines("", |_| Ok(())).len(), 1);
+    }
+}
@@ -0,0 +1,344 @@
+//! Private leaderboard exports, as downloaded from the leaderboard JSON API.
+//!
+//! Older exports store ids and timestamps as strings, both forms are accepted.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::json::Json;
+
+// Puzzles unlock at midnight EST
+const UNLOCK_HOUR_UTC: i64 = 5;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Member {
+    pub id: i64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: i64,
+    // Timestamp of each star, keyed by (day, part)
+    pub completions: BTreeMap<(u32, u32), i64>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    pub fn last_star(&self) -> Option<i64> {
+        self.completions.values().copied().max()
+    }
+
+    // Seconds between the two stars of a day
+    pub fn delta(&self, day: u32) -> Option<i64> {
+        Some(self.completions.get(&(day, 2))? - self.completions.get(&(day, 1))?)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scoring {
+    // Recomputed from the timestamps: N points for the first star of each part, N - 1 for
+    // the second...
+    Local,
+    // Most stars, ties broken by the earliest last star
+    Stars,
+    // Like local, but ranking the time between part 1 and part 2 of each day
+    Delta,
+}
+
+impl Scoring {
+    pub const ALL: [Scoring; 3] = [Scoring::Local, Scoring::Stars, Scoring::Delta];
+}
+
+impl FromStr for Scoring {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "local" => Ok(Scoring::Local),
+            "stars" => Ok(Scoring::Stars),
+            "delta" => Ok(Scoring::Delta),
+            _ => Err(format!(
+                "unknown scoring `{}`, expected local, stars or delta",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Scoring {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Scoring::Local => "local",
+            Scoring::Stars => "stars",
+            Scoring::Delta => "delta",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Leaderboard {
+    pub event: i32,
+    pub members: Vec<Member>,
+}
+
+// Accepts both `123` and `"123"`
+fn int(json: &Json, what: &str) -> Result<i64, String> {
+    match json {
+        Json::Int(i) => Ok(*i),
+        Json::String(s) => s.parse().map_err(|_| format!("invalid {} `{}`", what, s)),
+        _ => Err(format!("invalid {} {}", what, json)),
+    }
+}
+
+fn field<'a>(json: &'a Json, key: &str) -> Result<&'a Json, String> {
+    json.get(key).ok_or_else(|| format!("missing `{}`", key))
+}
+
+fn parse_member(json: &Json) -> Result<Member, String> {
+    let id = int(field(json, "id")?, "member id")?;
+    let name = json.get("name").and_then(Json::as_str).map(str::to_string);
+    let mut completions = BTreeMap::new();
+
+    if let Some(Json::Object(days)) = json.get("completion_day_level") {
+        for (day, parts) in days {
+            let day: u32 = day.parse().map_err(|_| format!("invalid day `{}`", day))?;
+            let Json::Object(parts) = parts else {
+                return Err(format!("invalid completions for day {}", day));
+            };
+
+            for (part, star) in parts {
+                let part: u32 = part
+                    .parse()
+                    .map_err(|_| format!("invalid part `{}`", part))?;
+                let ts = int(field(star, "get_star_ts")?, "timestamp")?;
+                completions.insert((day, part), ts);
+            }
+        }
+    }
+
+    Ok(Member {
+        id,
+        name,
+        stars: int(field(json, "stars")?, "stars")? as u32,
+        local_score: int(field(json, "local_score")?, "local score")?,
+        completions,
+    })
+}
+
+impl Leaderboard {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let json = Json::parse(s)?;
+        let event = int(field(&json, "event")?, "event")? as i32;
+
+        let Json::Object(members) = field(&json, "members")? else {
+            return Err("`members` is not an object".to_string());
+        };
+
+        let members = members
+            .iter()
+            .map(|(id, member)| parse_member(member).map_err(|e| format!("member {}: {}", id, e)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { event, members })
+    }
+
+    pub fn unlock(&self, day: u32) -> i64 {
+        days_from_civil(self.event, 12, day) * 86400 + UNLOCK_HOUR_UTC * 3600
+    }
+
+    pub fn days(&self) -> Vec<u32> {
+        let mut days: Vec<_> = self
+            .members
+            .iter()
+            .flat_map(|m| m.completions.keys().map(|&(day, _)| day))
+            .collect();
+        days.sort_unstable();
+        days.dedup();
+        days
+    }
+
+    // Members with their score, best first
+    pub fn rank(&self, scoring: Scoring) -> Vec<(&Member, i64)> {
+        let mut scores = vec![0; self.members.len()];
+        let n = self.members.len() as i64;
+
+        // Ranks the members having a value, the lowest value earning n points
+        let mut award = |values: Vec<(usize, i64)>| {
+            let mut values = values;
+            values.sort_by_key(|&(idx, value)| (value, idx));
+
+            for (rank, (idx, _)) in values.into_iter().enumerate() {
+                scores[idx] += n - rank as i64;
+            }
+        };
+
+        for day in self.days() {
+            match scoring {
+                Scoring::Local => {
+                    for part in [1, 2] {
+                        award(
+                            self.members
+                                .iter()
+                                .enumerate()
+                                .filter_map(|(idx, m)| {
+                                    Some((idx, *m.completions.get(&(day, part))?))
+                                })
+                                .collect(),
+                        );
+                    }
+                }
+                Scoring::Delta => award(
+                    self.members
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(idx, m)| Some((idx, m.delta(day)?)))
+                        .collect(),
+                ),
+                Scoring::Stars => {}
+            }
+        }
+
+        if scoring == Scoring::Stars {
+            for (idx, member) in self.members.iter().enumerate() {
+                scores[idx] = member.completions.len() as i64;
+            }
+        }
+
+        let mut ranking: Vec<_> = self.members.iter().zip(scores).collect();
+        ranking.sort_by_key(|(m, score)| (-score, m.last_star().unwrap_or(i64::MAX), m.id));
+        ranking
+    }
+}
+
+// Days since 1970-01-01 of a date of the proleptic Gregorian calendar
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year } as i64;
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146097 + doe - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+
+    (yoe + era * 400 + (month <= 2) as i64, month, day)
+}
+
+pub fn format_timestamp(ts: i64) -> String {
+    let (year, month, day) = civil_from_days(ts.div_euclid(86400));
+    let secs = ts.rem_euclid(86400);
+
+    format!(
+        "{}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+// Hours are not wrapped into days, a star earned a day late reads +24:00:00
+pub fn format_duration(secs: i64) -> String {
+    let sign = if secs < 0 { "-" } else { "" };
+    let secs = secs.abs();
+
+    format!(
+        "{}{}:{:02}:{:02}",
+        sign,
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const FIXTURE_PATH: &str = "tests/fixtures/leaderboard.json";
+
+    fn names(ranking: &[(&Member, i64)]) -> Vec<(String, i64)> {
+        ranking
+            .iter()
+            .map(|(m, score)| (m.display_name(), *score))
+            .collect()
+    }
+
+    #[test]
+    fn leaderboard_parse() {
+        let board = Leaderboard::parse(&fs::read_to_string(FIXTURE_PATH).unwrap()).unwrap();
+
+        assert_eq!(board.event, 2020);
+        assert_eq!(board.days(), vec![1, 2]);
+        assert_eq!(board.unlock(1), 1606798800);
+        assert_eq!(format_timestamp(board.unlock(2)), "2020-12-02 05:00:00");
+
+        let anonymous = &board.members[2];
+        assert_eq!(anonymous.display_name(), "(anonymous user #3)");
+        assert_eq!(anonymous.completions.get(&(1, 2)), Some(&1606802460));
+        assert_eq!(anonymous.delta(1), Some(60));
+        assert_eq!(board.members[1].delta(2), None);
+
+        assert!(Leaderboard::parse(r#"{"event": "2020"}"#).is_err());
+    }
+
+    #[test]
+    fn leaderboard_rank() {
+        let board = Leaderboard::parse(&fs::read_to_string(FIXTURE_PATH).unwrap()).unwrap();
+        let anonymous = "(anonymous user #3)".to_string();
+
+        // Matches the local scores of the export
+        assert_eq!(
+            names(&board.rank(Scoring::Local)),
+            vec![
+                ("alice".to_string(), 11),
+                ("bob".to_string(), 7),
+                (anonymous.clone(), 2)
+            ]
+        );
+        assert_eq!(
+            names(&board.rank(Scoring::Stars)),
+            vec![
+                ("alice".to_string(), 4),
+                ("bob".to_string(), 3),
+                (anonymous.clone(), 2)
+            ]
+        );
+        assert_eq!(
+            names(&board.rank(Scoring::Delta)),
+            vec![
+                ("alice".to_string(), 5),
+                (anonymous, 3),
+                ("bob".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn leaderboard_format() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(format_timestamp(1608872400), "2020-12-25 05:00:00");
+        assert_eq!(format_duration(90061), "25:01:01");
+    }
+}
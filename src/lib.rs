@@ -0,0 +1,39 @@
+pub mod budget;
+pub mod cache;
+pub mod config;
+pub mod difftest;
+pub mod explain;
+pub mod fuzz;
+pub mod history;
+pub mod identify;
+pub mod input;
+pub mod json;
+pub mod leaderboard;
+pub mod log;
+pub mod memory;
+pub mod params;
+pub mod progress;
+pub mod property;
+pub mod report;
+pub mod sandbox;
+pub mod snapshot;
+pub mod solution;
+pub mod stats;
+pub mod vault;
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
@@ -0,0 +1,205 @@
+//! Leveled diagnostics on stderr.
+//!
+//! Messages are logged with [`error!`], [`warn!`], [`info!`], [`debug!`] and [`trace!`],
+//! tagged with the module they come from, e.g. `day8`. The `AOC_LOG` environment variable
+//! selects what gets printed: a default level and per module levels, comma separated, such
+//! as `AOC_LOG=info,day8=trace`. Only warnings and errors are printed by default.
+
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+pub const ENV_VAR: &str = "AOC_LOG";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!("unknown log level `{}`", s)),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filter {
+    // None turns logging off, with `off`
+    default: Option<Level>,
+    targets: Vec<(String, Option<Level>)>,
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self {
+            default: Some(Level::Warn),
+            targets: vec![],
+        }
+    }
+}
+
+impl Filter {
+    // Invalid items are reported once and skipped, a typo should not stop a run
+    pub fn parse(spec: &str) -> (Self, Vec<String>) {
+        let mut filter = Filter::default();
+        let mut errors = vec![];
+
+        let level = |s: &str| match s {
+            "off" => Ok(None),
+            _ => s.parse().map(Some),
+        };
+
+        for item in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let parsed = match item.split_once('=') {
+                Some((target, value)) => level(value).map(|l| {
+                    filter.targets.push((target.to_string(), l));
+                }),
+                None => level(item).map(|l| filter.default = l),
+            };
+
+            if let Err(error) = parsed {
+                errors.push(format!("{}: {}", ENV_VAR, error));
+            }
+        }
+
+        (filter, errors)
+    }
+
+    pub fn enabled(&self, level: Level, target: &str) -> bool {
+        let max = self
+            .targets
+            .iter()
+            .rev()
+            .find(|(t, _)| t == target)
+            .map_or(self.default, |&(_, l)| l);
+
+        max.is_some_and(|max| level <= max)
+    }
+}
+
+fn filter() -> &'static Filter {
+    static FILTER: OnceLock<Filter> = OnceLock::new();
+
+    FILTER.get_or_init(|| {
+        let (filter, errors) = Filter::parse(&env::var(ENV_VAR).unwrap_or_default());
+        for error in errors {
+            eprintln!("[{} log] {}", Level::Warn, error);
+        }
+
+        filter
+    })
+}
+
+// `aoc2020::day8::parser` is logged as `day8`
+#[doc(hidden)]
+pub fn target(module_path: &str) -> &str {
+    module_path.split("::").nth(1).unwrap_or(module_path)
+}
+
+pub fn enabled(level: Level, target: &str) -> bool {
+    filter().enabled(level, target)
+}
+
+#[doc(hidden)]
+pub fn write(level: Level, target: &str, args: fmt::Arguments) {
+    eprintln!("[{} {}] {}", level, target, args);
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log {
+    ($level:expr, $($arg:tt)+) => {{
+        let target = $crate::log::target(module_path!());
+        if $crate::log::enabled($level, target) {
+            $crate::log::write($level, target, format_args!($($arg)+));
+        }
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_error {
+    ($($arg:tt)+) => { $crate::__log!($crate::log::Level::Error, $($arg)+) };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_warn {
+    ($($arg:tt)+) => { $crate::__log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_info {
+    ($($arg:tt)+) => { $crate::__log!($crate::log::Level::Info, $($arg)+) };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_debug {
+    ($($arg:tt)+) => { $crate::__log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_trace {
+    ($($arg:tt)+) => { $crate::__log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+pub use __log_debug as debug;
+pub use __log_error as error;
+pub use __log_info as info;
+pub use __log_trace as trace;
+pub use __log_warn as warn;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_filter() {
+        let (filter, errors) = Filter::parse("");
+        assert!(errors.is_empty());
+        assert!(filter.enabled(Level::Warn, "day8"));
+        assert!(!filter.enabled(Level::Info, "day8"));
+
+        let (filter, errors) = Filter::parse("info, day8=trace,day11=off,day3=loud");
+        assert_eq!(errors, vec!["AOC_LOG: unknown log level `loud`"]);
+        assert!(filter.enabled(Level::Trace, "day8"));
+        assert!(filter.enabled(Level::Info, "day3"));
+        assert!(!filter.enabled(Level::Debug, "day3"));
+        assert!(!filter.enabled(Level::Error, "day11"));
+
+        assert_eq!(target("aoc2020::day8"), "day8");
+        assert_eq!(target("aoc2020::day8::tests"), "day8");
+        assert_eq!(target("aoc"), "aoc");
+    }
+}
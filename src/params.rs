@@ -0,0 +1,185 @@
+//! Puzzle parameters, the constants of a puzzle statement that can be overridden.
+//!
+//! Each day declares its parameters with their defaults, the runner resolves them with
+//! the `--param name=value` overrides and passes them to the solvers.
+
+use std::str::FromStr;
+
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub help: &'static str,
+    pub valid: fn(&str) -> bool,
+    // Used by `aoc fuzz` instead of the default when that one makes every run slow
+    pub fuzz: Option<&'static str>,
+}
+
+// Validation of parameters holding a single value
+pub fn parses<T: FromStr>(value: &str) -> bool {
+    value.parse::<T>().is_ok()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Params {
+    values: Vec<(&'static str, String)>,
+}
+
+impl Params {
+    pub fn defaults(declared: &[Param]) -> Self {
+        Self {
+            values: declared
+                .iter()
+                .map(|param| (param.name, param.default.to_string()))
+                .collect(),
+        }
+    }
+
+    pub fn fuzzing(declared: &[Param]) -> Self {
+        Self {
+            values: declared
+                .iter()
+                .map(|param| (param.name, param.fuzz.unwrap_or(param.default).to_string()))
+                .collect(),
+        }
+    }
+
+    // Overrides are `name=value` strings
+    pub fn new(declared: &[Param], overrides: &[String]) -> Result<Self, String> {
+        let mut params = Self::defaults(declared);
+
+        for item in overrides {
+            let (name, value) = item
+                .split_once('=')
+                .ok_or_else(|| format!("expected `name=value`, got `{}`", item))?;
+
+            let Some(param) = declared.iter().find(|param| param.name == name) else {
+                let known: Vec<_> = declared.iter().map(|param| param.name).collect();
+                return Err(format!(
+                    "unknown parameter `{}`, expected one of: {}",
+                    name,
+                    if known.is_empty() {
+                        "(none)".to_string()
+                    } else {
+                        known.join(", ")
+                    }
+                ));
+            };
+
+            if !(param.valid)(value) {
+                return Err(format!(
+                    "invalid value `{}` for parameter `{}` ({})",
+                    value, name, param.help
+                ));
+            }
+
+            for (known, current) in &mut params.values {
+                if *known == name {
+                    *current = value.to_string();
+                }
+            }
+        }
+
+        Ok(params)
+    }
+
+    pub fn value(&self, name: &str) -> &str {
+        self.values
+            .iter()
+            .find(|(known, _)| *known == name)
+            .map(|(_, value)| value.as_str())
+            .unwrap_or_else(|| panic!("undeclared parameter `{}`", name))
+    }
+
+    // Values are validated by `new`, so a failure here is a bug in the declaration
+    pub fn get<T: FromStr>(&self, name: &str) -> T {
+        self.value(name)
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid value for parameter `{}`", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{Solution, SOLUTIONS};
+    use crate::{day1, day11, day15, day3, day9};
+
+    const DECLARED: [Param; 2] = [
+        Param {
+            name: "turns",
+            default: "2020",
+            help: "number of turns",
+            valid: parses::<u32>,
+            fuzz: Some("10"),
+        },
+        Param {
+            name: "prefix",
+            default: "departure",
+            help: "prefix of the fields",
+            valid: |_| true,
+            fuzz: None,
+        },
+    ];
+
+    #[test]
+    fn params_new() {
+        let params = Params::new(&DECLARED, &["turns=10".to_string()]).unwrap();
+        assert_eq!(params.get::<u32>("turns"), 10);
+        assert_eq!(params.value("prefix"), "departure");
+
+        assert!(Params::new(&DECLARED, &["turns=-1".to_string()]).is_err());
+        assert!(Params::new(&DECLARED, &["window=5".to_string()]).is_err());
+        assert!(Params::new(&DECLARED, &["turns".to_string()]).is_err());
+
+        let params = Params::fuzzing(&DECLARED);
+        assert_eq!(params.get::<u32>("turns"), 10);
+        assert_eq!(params.value("prefix"), "departure");
+    }
+
+    #[test]
+    fn params_defaults_are_valid() {
+        for solution in SOLUTIONS {
+            for param in solution.params {
+                for value in [Some(param.default), param.fuzz].into_iter().flatten() {
+                    assert!((param.valid)(value), "day {}: {}", solution.day, param.name);
+                }
+            }
+        }
+    }
+
+    // Defaults are written out as strings, the tests solve with the constants
+    #[test]
+    fn params_defaults_match_constants() {
+        let defaults = |solution: &Solution| Params::defaults(solution.params);
+
+        assert_eq!(defaults(&day1::SOLUTION).get::<u32>("target"), day1::TARGET);
+
+        assert_eq!(
+            day3::parse_slopes(defaults(&day3::SOLUTION).value("slopes")),
+            Some(day3::SLOPES.to_vec())
+        );
+
+        assert_eq!(
+            defaults(&day9::SOLUTION).get::<usize>("window"),
+            day9::WINDOW
+        );
+
+        assert_eq!(
+            defaults(&day11::SOLUTION).get::<usize>("part1_threshold"),
+            day11::PART1_THRESHOLD
+        );
+        assert_eq!(
+            defaults(&day11::SOLUTION).get::<usize>("part2_threshold"),
+            day11::PART2_THRESHOLD
+        );
+
+        assert_eq!(
+            defaults(&day15::SOLUTION).get::<u32>("part1_turns"),
+            day15::PART1_TURNS
+        );
+        assert_eq!(
+            defaults(&day15::SOLUTION).get::<u32>("part2_turns"),
+            day15::PART2_TURNS
+        );
+    }
+}
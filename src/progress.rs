@@ -0,0 +1,116 @@
+//! Progress reporting for the long-running solvers.
+//!
+//! Solvers take a [`Progress`] and update it with how much of their work is done. The
+//! runner renders it as a single line on stderr when it is a terminal, everywhere else
+//! updates are ignored.
+
+use std::cell::Cell;
+use std::io::{self, IsTerminal};
+use std::time::{Duration, Instant};
+
+const THROTTLE: Duration = Duration::from_millis(100);
+
+type Sink = Box<dyn Fn(&str)>;
+
+pub struct Progress {
+    label: String,
+    sink: Option<Sink>,
+    last_render: Cell<Option<Instant>>,
+}
+
+impl Progress {
+    pub fn none() -> Self {
+        Self {
+            label: String::new(),
+            sink: None,
+            last_render: Cell::new(None),
+        }
+    }
+
+    pub fn terminal(label: impl Into<String>) -> Self {
+        if !io::stderr().is_terminal() {
+            return Self::none();
+        }
+
+        Self::with_sink(label, |line| eprint!("\r{}\x1b[K", line))
+    }
+
+    fn with_sink(label: impl Into<String>, sink: impl Fn(&str) + 'static) -> Self {
+        Self {
+            label: label.into(),
+            sink: Some(Box::new(sink)),
+            last_render: Cell::new(None),
+        }
+    }
+
+    // Cheap when not rendering, still meant to be called every few thousand steps at most
+    pub fn update(&self, current: u64, total: u64) {
+        self.render(|| {
+            format!(
+                "{}: {}/{} ({:.0}%)",
+                self.label,
+                current,
+                total,
+                100.0 * current as f64 / total.max(1) as f64
+            )
+        });
+    }
+
+    fn render(&self, line: impl FnOnce() -> String) {
+        let Some(sink) = &self.sink else {
+            return;
+        };
+
+        let now = Instant::now();
+        if self
+            .last_render
+            .get()
+            .is_some_and(|last| now - last < THROTTLE)
+        {
+            return;
+        }
+
+        self.last_render.set(Some(now));
+        sink(&line());
+    }
+
+    // For work of unknown length
+    pub fn count(&self, current: u64) {
+        self.render(|| format!("{}: {}", self.label, current));
+    }
+}
+
+// Clears the progress line before the answer gets printed
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if let (Some(sink), Some(_)) = (&self.sink, self.last_render.get()) {
+            sink("");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn progress_throttle() {
+        let lines = Rc::new(RefCell::new(vec![]));
+        let sink_lines = lines.clone();
+
+        let progress = Progress::with_sink("turns", move |line| {
+            sink_lines.borrow_mut().push(line.to_string())
+        });
+        for turn in 1..=1000 {
+            progress.update(turn, 4000);
+        }
+        drop(progress);
+
+        assert_eq!(*lines.borrow(), vec!["turns: 1/4000 (0%)", ""]);
+
+        // Silent handles never render
+        Progress::none().update(1, 2);
+    }
+}
@@ -0,0 +1,328 @@
+//! Property-based testing with seeded generators and shrinking.
+//!
+//! A [`Property`] pairs a generator of random inputs with an invariant. [`check`] tests the
+//! invariant on many inputs and, on the first failure, shrinks the input down to a minimal
+//! failing case. The seed is fixed, set [`SEED_VAR`] to try other inputs or to replay a
+//! failure found with another seed.
+
+use std::env;
+use std::fmt::{self, Debug};
+use std::ops::RangeInclusive;
+
+use crate::{day4, day5, day8};
+
+pub const SEED_VAR: &str = "PROPERTY_SEED";
+// Fixed so that a failing run fails the same way every time
+pub const DEFAULT_SEED: u64 = 2020;
+const MAX_GENERATE_ATTEMPTS: usize = 1000;
+
+// SplitMix64, good enough for test input generation and accepts any seed
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let span = (range.end() - range.start()) as u64 + 1;
+        range.start() + self.below(span) as i64
+    }
+}
+
+pub trait Shrink: Sized {
+    // Candidates strictly simpler than self, most aggressive first
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! impl_shrink_unsigned {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = vec![0, self / 2, self.saturating_sub(1)];
+                candidates.dedup();
+                candidates.retain(|x| x < self);
+                candidates
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_shrink_signed {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = vec![0, self / 2, self - self.signum()];
+                candidates.dedup();
+                candidates.retain(|x| x.unsigned_abs() < self.unsigned_abs());
+                candidates
+            }
+        }
+    )*};
+}
+
+impl_shrink_unsigned!(u8, u32, u64, usize);
+impl_shrink_signed!(i32, i64);
+
+impl<A: Shrink + Clone, B: Shrink + Clone> Shrink for (A, B) {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates: Vec<Self> = self
+            .0
+            .shrink()
+            .into_iter()
+            .map(|a| (a, self.1.clone()))
+            .collect();
+
+        candidates.extend(self.1.shrink().into_iter().map(|b| (self.0.clone(), b)));
+        candidates
+    }
+}
+
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = vec![];
+
+        // Drop elements first, then simplify them one by one
+        for idx in 0..self.len() {
+            let mut v = self.clone();
+            v.remove(idx);
+            candidates.push(v);
+        }
+
+        for (idx, item) in self.iter().enumerate() {
+            for smaller in item.shrink() {
+                let mut v = self.clone();
+                v[idx] = smaller;
+                candidates.push(v);
+            }
+        }
+
+        candidates
+    }
+}
+
+impl Shrink for char {
+    fn shrink(&self) -> Vec<Self> {
+        if *self == 'a' {
+            vec![]
+        } else {
+            vec!['a']
+        }
+    }
+}
+
+impl Shrink for String {
+    fn shrink(&self) -> Vec<Self> {
+        let chars: Vec<char> = self.chars().collect();
+
+        chars
+            .shrink()
+            .into_iter()
+            .map(|chars| chars.into_iter().collect())
+            .collect()
+    }
+}
+
+pub struct Property<I> {
+    pub name: &'static str,
+    pub generate: fn(&mut Rng) -> I,
+    // Inputs rejected here are neither generated nor reached while shrinking
+    pub precondition: fn(&I) -> bool,
+    // Explains why the invariant does not hold
+    pub holds: fn(&I) -> Result<(), String>,
+}
+
+#[derive(Debug)]
+pub struct Failure<I> {
+    pub name: &'static str,
+    pub seed: u64,
+    pub case: usize,
+    pub original: I,
+    pub input: I,
+    pub message: String,
+}
+
+impl<I: Debug> fmt::Display for Failure<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{}: property failed on case {} (rerun with {}={})",
+            self.name, self.case, SEED_VAR, self.seed
+        )?;
+        writeln!(f, "  original input: {:?}", self.original)?;
+        writeln!(f, "  shrunk input:   {:?}", self.input)?;
+        write!(f, "  {}", self.message)
+    }
+}
+
+fn shrink<I: Shrink>(property: &Property<I>, mut input: I) -> I {
+    'outer: loop {
+        for candidate in input.shrink() {
+            if (property.precondition)(&candidate) && (property.holds)(&candidate).is_err() {
+                input = candidate;
+                continue 'outer;
+            }
+        }
+
+        return input;
+    }
+}
+
+fn generate<I>(
+    name: &str,
+    generate: fn(&mut Rng) -> I,
+    precondition: fn(&I) -> bool,
+    rng: &mut Rng,
+) -> I {
+    for _ in 0..MAX_GENERATE_ATTEMPTS {
+        let input = generate(rng);
+        if precondition(&input) {
+            return input;
+        }
+    }
+
+    panic!("{}: generator keeps failing the precondition", name);
+}
+
+pub fn check<I: Shrink + Clone>(
+    property: &Property<I>,
+    seed: u64,
+    cases: usize,
+) -> Result<(), Failure<I>> {
+    let mut rng = Rng::new(seed);
+
+    for case in 0..cases {
+        let original = generate(
+            property.name,
+            property.generate,
+            property.precondition,
+            &mut rng,
+        );
+
+        if (property.holds)(&original).is_err() {
+            let input = shrink(property, original.clone());
+            let message = (property.holds)(&input).unwrap_err();
+
+            return Err(Failure {
+                name: property.name,
+                seed,
+                case,
+                original,
+                input,
+                message,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// Shorthand for round trip invariants
+pub fn expect_eq<T: PartialEq + Debug>(expected: T, actual: T) -> Result<(), String> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(format!("expected {:?}, got {:?}", expected, actual))
+    }
+}
+
+// Type-erased property, so properties with different input types can share a registry
+pub trait PropertyTest {
+    fn name(&self) -> &'static str;
+    fn run(&self, seed: u64, cases: usize) -> Result<(), String>;
+}
+
+impl<I: Shrink + Clone + Debug> PropertyTest for Property<I> {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn run(&self, seed: u64, cases: usize) -> Result<(), String> {
+        check(self, seed, cases).map_err(|failure| failure.to_string())
+    }
+}
+
+pub fn registry() -> Vec<Box<dyn PropertyTest>> {
+    let mut properties = vec![];
+
+    properties.extend(day4::properties());
+    properties.extend(day5::properties());
+    properties.extend(day8::properties());
+
+    properties
+}
+
+// Seed from the environment when exploring other inputs or reproducing a failure
+pub fn seed() -> u64 {
+    match env::var(SEED_VAR) {
+        Ok(seed) => seed
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid {} `{}`", SEED_VAR, seed)),
+        Err(_) => DEFAULT_SEED,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CASES: usize = 300;
+
+    #[test]
+    fn property_registry() {
+        let seed = seed();
+
+        for property in registry() {
+            if let Err(failure) = property.run(seed, CASES) {
+                panic!("{}", failure);
+            }
+        }
+    }
+
+    #[test]
+    fn property_shrink() {
+        let property = Property {
+            name: "short strings",
+            generate: |rng| {
+                (0..rng.below(20))
+                    .map(|_| (b'a' + rng.below(26) as u8) as char)
+                    .collect()
+            },
+            precondition: |_| true,
+            holds: |s: &String| {
+                if s.len() < 5 {
+                    Ok(())
+                } else {
+                    Err(format!("{} chars", s.len()))
+                }
+            },
+        };
+
+        let failure = check(&property, 42, 100).unwrap_err();
+        assert_eq!(failure.input, "aaaaa");
+        assert_eq!(failure.message, "5 chars");
+    }
+
+    #[test]
+    fn property_shrink_signed() {
+        assert_eq!(i32::MIN.shrink(), [0, i32::MIN / 2, i32::MIN + 1]);
+        assert_eq!(i32::MAX.shrink(), [0, i32::MAX / 2, i32::MAX - 1]);
+        assert_eq!((-3i64).shrink(), [0, -1, -2]);
+        assert!(0i32.shrink().is_empty());
+    }
+}
@@ -0,0 +1,225 @@
+//! Markdown summary of the days, for the README.
+//!
+//! The table goes between the [`START_MARKER`] and [`END_MARKER`] lines of the README, so
+//! regenerating it leaves the rest of the file untouched.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::json::Json;
+use crate::solution::DEFAULT_VARIANT;
+
+pub const START_MARKER: &str = "<!-- aoc report -->";
+pub const END_MARKER: &str = "<!-- /aoc report -->";
+
+pub struct PartReport {
+    pub part: u32,
+    // The answer of the runner, or why there is none
+    pub answer: Result<String, String>,
+    pub expected: Option<String>,
+    pub median: Option<Duration>,
+}
+
+impl PartReport {
+    pub fn is_star(&self) -> bool {
+        matches!((&self.answer, &self.expected), (Ok(answer), Some(expected)) if answer == expected)
+    }
+
+    fn answer_cell(&self, redact: bool) -> String {
+        let answer = match &self.answer {
+            Err(error) => return escape(error),
+            Ok(_) if redact => "*redacted*".to_string(),
+            Ok(answer) => format!("`{}`", escape(answer)),
+        };
+
+        match self.expected {
+            Some(_) if !self.is_star() => format!("{} ✗", answer),
+            _ => answer,
+        }
+    }
+}
+
+pub struct DayReport {
+    pub day: u32,
+    pub source: String,
+    pub parts: Vec<PartReport>,
+}
+
+impl DayReport {
+    fn part(&self, part: u32) -> Option<&PartReport> {
+        self.parts.iter().find(|p| p.part == part)
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+// Median of each (day, part), from the output of `aoc bench --format json`
+pub fn medians(bench: &Json) -> Result<HashMap<(u32, u32), Duration>, String> {
+    let Json::Array(results) = bench else {
+        return Err("expected an array of bench results".to_string());
+    };
+
+    // `aoc bench --compare` also times the other implementations, the report shows the default
+    results
+        .iter()
+        .filter(|result| {
+            result
+                .get("impl")
+                .and_then(Json::as_str)
+                .is_none_or(|name| name == DEFAULT_VARIANT)
+        })
+        .map(|result| {
+            let int = |key| {
+                result
+                    .get(key)
+                    .and_then(Json::as_i64)
+                    .ok_or_else(|| format!("missing `{}` in bench result", key))
+            };
+
+            let key = (int("day")? as u32, int("part")? as u32);
+            Ok((key, Duration::from_micros(int("median_us")? as u64)))
+        })
+        .collect()
+}
+
+pub fn markdown(days: &[DayReport], redact: bool) -> String {
+    let mut out = String::new();
+    out.push_str("| Day | Stars | Part 1 | Part 2 | Part 1 median | Part 2 median | Source |\n");
+    out.push_str("|----:|:------|:-------|:-------|-------------:|-------------:|:-------|\n");
+
+    for day in days {
+        let stars = day.parts.iter().filter(|p| p.is_star()).count();
+        let mut row = vec![day.day.to_string(), "★".repeat(stars)];
+
+        for part in [1, 2] {
+            row.push(match day.part(part) {
+                Some(part) => part.answer_cell(redact),
+                None => "-".to_string(),
+            });
+        }
+
+        for part in [1, 2] {
+            row.push(match day.part(part).and_then(|p| p.median) {
+                Some(median) => format!("{:?}", median),
+                None => "-".to_string(),
+            });
+        }
+
+        row.push(format!("[{}]({})", day.source, day.source));
+        out.push_str(&format!("| {} |\n", row.join(" | ")));
+    }
+
+    let stars: usize = days
+        .iter()
+        .map(|day| day.parts.iter().filter(|p| p.is_star()).count())
+        .sum();
+    out.push_str(&format!("\n**{}** stars\n", stars));
+
+    out
+}
+
+// Replaces what is between the markers, keeping the marker lines
+pub fn update_section(readme: &str, section: &str) -> Result<String, String> {
+    let start = readme
+        .find(START_MARKER)
+        .ok_or_else(|| format!("missing `{}` marker", START_MARKER))?;
+    let end = readme[start..]
+        .find(END_MARKER)
+        .map(|end| start + end)
+        .ok_or_else(|| format!("missing `{}` marker after `{}`", END_MARKER, START_MARKER))?;
+
+    Ok(format!(
+        "{}\n{}{}",
+        &readme[..start + START_MARKER.len()],
+        section,
+        &readme[end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days() -> Vec<DayReport> {
+        vec![
+            DayReport {
+                day: 1,
+                source: "src/day1/mod.rs".to_string(),
+                parts: vec![
+                    PartReport {
+                        part: 1,
+                        answer: Ok("42".to_string()),
+                        expected: Some("42".to_string()),
+                        median: Some(Duration::from_micros(1500)),
+                    },
+                    PartReport {
+                        part: 2,
+                        answer: Ok("7".to_string()),
+                        expected: Some("8".to_string()),
+                        median: None,
+                    },
+                ],
+            },
+            DayReport {
+                day: 13,
+                source: "src/day13/mod.rs".to_string(),
+                parts: vec![PartReport {
+                    part: 1,
+                    answer: Err("timed out after 1.00s".to_string()),
+                    expected: None,
+                    median: None,
+                }],
+            },
+        ]
+    }
+
+    #[test]
+    fn report_markdown() {
+        let header = "| Day | Stars | Part 1 | Part 2 | Part 1 median | Part 2 median | Source |\n\
+                      |----:|:------|:-------|:-------|-------------:|-------------:|:-------|\n";
+
+        assert_eq!(
+            markdown(&days(), false),
+            format!(
+                "{}{}{}\n**1** stars\n",
+                header,
+                "| 1 | ★ | `42` | `7` ✗ | 1.5ms | - | [src/day1/mod.rs](src/day1/mod.rs) |\n",
+                "| 13 |  | timed out after 1.00s | - | - | - | [src/day13/mod.rs](src/day13/mod.rs) |\n",
+            )
+        );
+
+        let redacted = markdown(&days(), true);
+        assert!(
+            redacted.contains("| *redacted* | *redacted* ✗ |"),
+            "{}",
+            redacted
+        );
+        assert!(!redacted.contains("42"));
+    }
+
+    #[test]
+    fn report_update_section() {
+        let readme = format!("# AoC\n\n{}\nold\n{}\n\nmore\n", START_MARKER, END_MARKER);
+        assert_eq!(
+            update_section(&readme, "new\n").unwrap(),
+            format!("# AoC\n\n{}\nnew\n{}\n\nmore\n", START_MARKER, END_MARKER)
+        );
+
+        assert!(update_section("# AoC\n", "new\n").is_err());
+        assert!(update_section(&format!("{}\n{}\n", END_MARKER, START_MARKER), "").is_err());
+    }
+
+    #[test]
+    fn report_medians() {
+        let bench = Json::parse(
+            r#"[{"day": 1, "part": 2, "answer": "7", "median_us": 1500},
+                {"day": 1, "part": 2, "impl": "hashing", "answer": "7", "median_us": 20}]"#,
+        );
+        let medians = medians(&bench.unwrap()).unwrap();
+        assert_eq!(medians[&(1, 2)], Duration::from_micros(1500));
+
+        assert!(super::medians(&Json::parse(r#"[{"day": 1}]"#).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,275 @@
+//! Resource limits of the child processes of `aoc run --sandbox`.
+//!
+//! The limits are set with `setrlimit` in the child, between `fork` and `exec`, so a
+//! runaway solver fails its own process instead of the terminal. [`Violation::classify`]
+//! tells from the exit status and the stderr of the child which limit it ran into. Only
+//! Linux is supported, the numbers of the limits and signals differing between systems.
+
+use std::fmt;
+use std::process::{Command, ExitStatus};
+
+use crate::json::{Json, ToJson};
+
+pub const SUPPORTED: bool = cfg!(target_os = "linux");
+
+pub const DEFAULT_MEMORY_MIB: u64 = 1024;
+pub const DEFAULT_CPU_SECS: u64 = 120;
+
+// Written by the default allocation error handler before it aborts
+const ALLOCATION_FAILED: &str = "memory allocation of";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Limits {
+    pub memory_mib: u64,
+    pub cpu_secs: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            memory_mib: DEFAULT_MEMORY_MIB,
+            cpu_secs: DEFAULT_CPU_SECS,
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod rlimit {
+    use std::ffi::{c_int, c_ulong};
+    use std::io;
+
+    pub const CPU: c_int = 0;
+    #[cfg(not(any(target_arch = "mips", target_arch = "mips64")))]
+    pub const ADDRESS_SPACE: c_int = 9;
+    #[cfg(any(target_arch = "mips", target_arch = "mips64"))]
+    pub const ADDRESS_SPACE: c_int = 6;
+
+    // `rlim_t`, with `RLIM_INFINITY` as its largest value
+    #[repr(C)]
+    struct RLimit {
+        current: c_ulong,
+        max: c_ulong,
+    }
+
+    extern "C" {
+        fn setrlimit(resource: c_int, limit: *const RLimit) -> c_int;
+    }
+
+    // Async-signal-safe, as needed between fork and exec
+    pub fn set(resource: c_int, current: u64, max: u64) -> io::Result<()> {
+        let limit = RLimit {
+            current: c_ulong::try_from(current).unwrap_or(c_ulong::MAX),
+            max: c_ulong::try_from(max).unwrap_or(c_ulong::MAX),
+        };
+
+        match unsafe { setrlimit(resource, &limit) } {
+            0 => Ok(()),
+            _ => Err(io::Error::last_os_error()),
+        }
+    }
+}
+
+impl Limits {
+    // The memory limit makes allocations fail. The CPU one raises SIGXCPU, the kernel
+    // sending SIGKILL instead when the hard limit is reached, hence a second above
+    #[cfg(target_os = "linux")]
+    pub fn apply(self, command: &mut Command) -> Result<(), String> {
+        use std::os::unix::process::CommandExt;
+
+        let memory = self.memory_mib << 20;
+        unsafe {
+            command.pre_exec(move || {
+                rlimit::set(rlimit::ADDRESS_SPACE, memory, memory)?;
+                rlimit::set(rlimit::CPU, self.cpu_secs, self.cpu_secs + 1)
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn apply(self, _command: &mut Command) -> Result<(), String> {
+        Err("resource limits need Linux".to_string())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Violation {
+    Memory { limit_mib: u64 },
+    Cpu { limit_secs: u64 },
+    // Killed by another signal
+    Signal(i32),
+    // Exited on its own, with its panic or the last line it wrote to stderr
+    Exit { code: i32, message: String },
+}
+
+#[cfg(unix)]
+fn signal(status: &ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(not(unix))]
+fn signal(_status: &ExitStatus) -> Option<i32> {
+    None
+}
+
+// The panic message reads `thread 'name' panicked at LOCATION:` then the payload
+fn exit_message(stderr: &str) -> String {
+    let mut lines = stderr.lines();
+
+    while let Some(line) = lines.next() {
+        if let Some((_, location)) = line.split_once(" panicked at ") {
+            let payload = lines.next().unwrap_or("");
+            return format!("{} at {}", payload, location.trim_end_matches(':'));
+        }
+    }
+
+    let last = stderr.lines().rev().find(|line| !line.trim().is_empty());
+    last.unwrap_or("").to_string()
+}
+
+const SIGABRT: i32 = 6;
+#[cfg(not(any(target_arch = "mips", target_arch = "mips64")))]
+const SIGXCPU: i32 = 24;
+#[cfg(any(target_arch = "mips", target_arch = "mips64"))]
+const SIGXCPU: i32 = 30;
+
+impl Violation {
+    // `expected` are the exit codes of a child that ran to its end
+    pub fn classify(
+        status: &ExitStatus,
+        stderr: &str,
+        limits: Limits,
+        expected: &[i32],
+    ) -> Option<Self> {
+        if let Some(code) = status.code() {
+            if expected.contains(&code) {
+                return None;
+            }
+
+            return Some(Violation::Exit {
+                code,
+                message: exit_message(stderr),
+            });
+        }
+
+        // A SIGKILL may come from the OOM killer as well as from anyone else
+        Some(match signal(status)? {
+            SIGABRT if stderr.contains(ALLOCATION_FAILED) => Violation::Memory {
+                limit_mib: limits.memory_mib,
+            },
+            SIGXCPU => Violation::Cpu {
+                limit_secs: limits.cpu_secs,
+            },
+            signal => Violation::Signal(signal),
+        })
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Violation::Memory { .. } => "memory",
+            Violation::Cpu { .. } => "cpu",
+            Violation::Signal(_) => "signal",
+            Violation::Exit { .. } => "exit",
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::Memory { limit_mib } => {
+                write!(f, "memory limit of {} MiB exceeded", limit_mib)
+            }
+            Violation::Cpu { limit_secs } => write!(f, "CPU limit of {}s exceeded", limit_secs),
+            Violation::Signal(signal) => write!(f, "killed by signal {}", signal),
+            Violation::Exit { code, message } => {
+                write!(f, "exited with code {}: {}", code, message)
+            }
+        }
+    }
+}
+
+impl ToJson for Violation {
+    fn to_json(&self) -> Json {
+        let mut fields = vec![("kind", self.kind().to_json())];
+
+        match self {
+            Violation::Memory { limit_mib } => fields.push(("limit_mib", limit_mib.to_json())),
+            Violation::Cpu { limit_secs } => fields.push(("limit_secs", limit_secs.to_json())),
+            Violation::Signal(signal) => fields.push(("signal", signal.to_json())),
+            Violation::Exit { code, message } => {
+                fields.push(("code", code.to_json()));
+                fields.push(("message", message.to_json()));
+            }
+        }
+
+        Json::object(fields)
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+
+    #[test]
+    fn sandbox_limits() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "ulimit -v; ulimit -t; ulimit -Ht"]);
+        Limits {
+            memory_mib: 256,
+            cpu_secs: 7,
+        }
+        .apply(&mut command)
+        .unwrap();
+
+        let output = command.output().unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "262144\n7\n8\n");
+    }
+
+    #[test]
+    fn sandbox_classify() {
+        let limits = Limits::default();
+        let classify =
+            |raw, stderr| Violation::classify(&ExitStatus::from_raw(raw), stderr, limits, &[0, 1]);
+
+        assert_eq!(classify(0, ""), None);
+        assert_eq!(classify(1 << 8, ""), None);
+        assert_eq!(
+            classify(SIGABRT, "memory allocation of 68719476736 bytes failed\n"),
+            Some(Violation::Memory { limit_mib: 1024 })
+        );
+        assert_eq!(
+            classify(SIGXCPU, ""),
+            Some(Violation::Cpu { limit_secs: 120 })
+        );
+        assert_eq!(classify(9, ""), Some(Violation::Signal(9)));
+        assert_eq!(
+            classify(SIGABRT, "assertion failed"),
+            Some(Violation::Signal(SIGABRT))
+        );
+        assert_eq!(
+            classify(
+                101 << 8,
+                "thread 'main' panicked at src/day7/mod.rs:12:5:\nno bag\n\n"
+            ),
+            Some(Violation::Exit {
+                code: 101,
+                message: "no bag at src/day7/mod.rs:12:5".to_string()
+            })
+        );
+        assert_eq!(
+            classify(2 << 8, "aoc: cannot read x.txt\n"),
+            Some(Violation::Exit {
+                code: 2,
+                message: "aoc: cannot read x.txt".to_string()
+            })
+        );
+        assert_eq!(
+            Violation::Memory { limit_mib: 64 }.to_json().to_string(),
+            r#"{"kind": "memory", "limit_mib": 64}"#
+        );
+    }
+}
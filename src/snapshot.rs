@@ -0,0 +1,206 @@
+//! Snapshot testing of textual renderings.
+//!
+//! [`assert_snapshot!`] compares a rendering with `snapshots/<name>.snap` next to the
+//! calling source file, failing with a line diff on mismatch. Set `UPDATE_SNAPSHOTS=1`
+//! to write the current renderings instead.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const ENV_VAR: &str = "UPDATE_SNAPSHOTS";
+
+// Unchanged lines shown around each change of a diff
+const CONTEXT: usize = 2;
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert_snapshot {
+    ($name:expr, $actual:expr) => {
+        if let Err(msg) =
+            $crate::snapshot::check($crate::snapshot::path(file!(), $name), &$actual.to_string())
+        {
+            panic!("{}", msg);
+        }
+    };
+}
+
+// Compares a rendering with its snapshot, panicking with a diff on mismatch
+pub use __assert_snapshot as assert_snapshot;
+
+pub fn path(source_file: &str, name: &str) -> PathBuf {
+    Path::new(source_file)
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join("snapshots")
+        .join(format!("{}.snap", name))
+}
+
+fn updating() -> bool {
+    env::var(ENV_VAR).is_ok_and(|value| !value.is_empty() && value != "0")
+}
+
+pub fn check(path: PathBuf, actual: &str) -> Result<(), String> {
+    compare(path, actual, updating())
+}
+
+fn compare(path: PathBuf, actual: &str, update: bool) -> Result<(), String> {
+    let expected = fs::read_to_string(&path).ok();
+
+    if expected.as_deref() == Some(actual) {
+        return Ok(());
+    }
+
+    if update {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        return fs::write(&path, actual).map_err(|e| format!("{}: {}", path.display(), e));
+    }
+
+    match expected {
+        None => Err(format!(
+            "snapshot {} does not exist, run with {}=1 to create it",
+            path.display(),
+            ENV_VAR
+        )),
+        Some(expected) => Err(format!(
+            "snapshot {} does not match, run with {}=1 to update it\n{}",
+            path.display(),
+            ENV_VAR,
+            diff(&expected, actual)
+        )),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+// Longest common subsequence of the lines, walked back into removals and additions
+fn diff_lines<'a>(expected: &'a str, actual: &'a str) -> Vec<Line<'a>> {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(Line::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lengths[i + 1][j] >= lengths[i][j + 1]) {
+            lines.push(Line::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Added(new[j]));
+            j += 1;
+        }
+    }
+
+    lines
+}
+
+// Renders the changed lines prefixed with `-`/`+`, with some context around them
+pub fn diff(expected: &str, actual: &str) -> String {
+    let lines = diff_lines(expected, actual);
+    let changed: Vec<usize> = (0..lines.len())
+        .filter(|&i| !matches!(lines[i], Line::Same(_)))
+        .collect();
+
+    let mut out = String::new();
+    let mut last = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        let near = changed
+            .iter()
+            .any(|&c| c.saturating_sub(CONTEXT) <= i && i <= c + CONTEXT);
+        if !near {
+            continue;
+        }
+
+        if last.map_or(i > 0, |last: usize| last + 1 < i) {
+            out.push_str("...\n");
+        }
+        last = Some(i);
+
+        let (prefix, text) = match line {
+            Line::Same(text) => (' ', text),
+            Line::Removed(text) => ('-', text),
+            Line::Added(text) => ('+', text),
+        };
+        out.push_str(&format!("{} {}\n", prefix, text));
+    }
+
+    if last.is_some_and(|last| last + 1 < lines.len()) {
+        out.push_str("...\n");
+    }
+
+    if expected.ends_with('\n') != actual.ends_with('\n') {
+        out.push_str("(trailing newline differs)\n");
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_diff() {
+        assert_eq!(
+            diff_lines("a\nb\nc", "a\nx\nc\nd"),
+            vec![
+                Line::Same("a"),
+                Line::Removed("b"),
+                Line::Added("x"),
+                Line::Same("c"),
+                Line::Added("d"),
+            ]
+        );
+
+        assert_eq!(
+            diff("a\nb\nc\nd\ne\nf\ng", "a\nb\nc\nd\ne\nf\nG"),
+            "...\n  e\n  f\n- g\n+ G\n"
+        );
+        assert_eq!(
+            diff("1\n2\n3\n4\n5\n6\n7", "0\n2\n3\n4\n5\n6\n7"),
+            "- 1\n+ 0\n  2\n  3\n...\n"
+        );
+        assert_eq!(diff("a", "a\n"), "(trailing newline differs)\n");
+    }
+
+    #[test]
+    fn snapshot_check() {
+        let dir = std::env::temp_dir().join(format!("aoc-snapshot-{}", std::process::id()));
+        let path = dir.join("missing.snap");
+
+        let err = compare(path.clone(), "text", false).unwrap_err();
+        assert!(err.contains("does not exist"), "{}", err);
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, "text").unwrap();
+        assert_eq!(compare(path.clone(), "text", false), Ok(()));
+        let err = compare(path.clone(), "other", false).unwrap_err();
+        assert!(err.contains("- text\n+ other"), "{}", err);
+        assert_eq!(compare(path.clone(), "other", true), Ok(()));
+        assert_eq!(fs::read_to_string(path).unwrap(), "other");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,256 @@
+//! Registry of the days, used by the `aoc` runner and the day binaries.
+//!
+//! The accepted answers of an input are kept next to it, `input.txt` having its answers in
+//! `input.answers`, one per line in part order.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+use crate::fuzz;
+use crate::input::CheckResult;
+use crate::log;
+use crate::params::{Param, Params};
+use crate::progress::Progress;
+use crate::vault;
+use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day2, day3, day4, day5, day6, day7,
+    day8, day9,
+};
+
+pub type Solver = fn(input: &str, params: &Params, progress: &Progress) -> String;
+
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub day: u32,
+    pub input_path: &'static str,
+    // Bump when a change to the solver may change its answers, to invalidate the cache
+    pub version: u32,
+    // Inputs given in the puzzle statement
+    pub examples: &'static [&'static str],
+    // Constants of the puzzle statement, overridable from the runner
+    pub params: &'static [Param],
+    // Parser only, dumps the parsed structure as JSON
+    pub check_input: fn(&str) -> CheckResult,
+    pub part1: Solver,
+    pub part2: Option<Solver>,
+    // Other implementations of the parts, selected by name from the runner
+    pub variants: &'static [Variant],
+}
+
+// A part left out falls back to the default implementation
+#[derive(Clone, Copy)]
+pub struct Variant {
+    pub name: &'static str,
+    pub part1: Option<Solver>,
+    pub part2: Option<Solver>,
+}
+
+pub const DEFAULT_VARIANT: &str = "default";
+
+impl Variant {
+    // Only the parts the variant implements itself
+    pub fn solver(&self, part: u32) -> Option<Solver> {
+        match part {
+            1 => self.part1,
+            2 => self.part2,
+            _ => None,
+        }
+    }
+}
+
+impl Solution {
+    pub fn parts(&self) -> Vec<(u32, Solver)> {
+        let mut parts = vec![(1, self.part1)];
+        parts.extend(self.part2.map(|part2| (2, part2)));
+        parts
+    }
+
+    pub fn variant_names(&self) -> Vec<&'static str> {
+        let mut names = vec![DEFAULT_VARIANT];
+        names.extend(self.variants.iter().map(|variant| variant.name));
+        names
+    }
+
+    // The solution with the parts of the named variant
+    pub fn variant(&self, name: &str) -> Result<Solution, String> {
+        if name == DEFAULT_VARIANT {
+            return Ok(*self);
+        }
+
+        let variant = self
+            .variants
+            .iter()
+            .find(|variant| variant.name == name)
+            .ok_or_else(|| {
+                format!(
+                    "day {} has no implementation `{}`, expected one of: {}",
+                    self.day,
+                    name,
+                    self.variant_names().join(", ")
+                )
+            })?;
+
+        Ok(Solution {
+            part1: variant.part1.unwrap_or(self.part1),
+            part2: variant.part2.or(self.part2),
+            ..*self
+        })
+    }
+
+    pub fn solver(&self, part: u32) -> Option<Solver> {
+        match part {
+            1 => Some(self.part1),
+            2 => self.part2,
+            _ => None,
+        }
+    }
+
+    // Accepted answers of the day's own input
+    pub fn answers(&self) -> Option<Vec<String>> {
+        read_answers(Path::new(self.input_path))
+    }
+}
+
+pub const ANSWERS_EXTENSION: &str = "answers";
+
+pub fn read_answers(input_path: &Path) -> Option<Vec<String>> {
+    let content = fs::read_to_string(input_path.with_extension(ANSWERS_EXTENSION)).ok()?;
+    Some(
+        content
+            .lines()
+            .map(|line| line.trim().to_string())
+            .collect(),
+    )
+}
+
+pub const SOLUTIONS: [&Solution; 16] = [
+    &day1::SOLUTION,
+    &day2::SOLUTION,
+    &day3::SOLUTION,
+    &day4::SOLUTION,
+    &day5::SOLUTION,
+    &day6::SOLUTION,
+    &day7::SOLUTION,
+    &day8::SOLUTION,
+    &day9::SOLUTION,
+    &day10::SOLUTION,
+    &day11::SOLUTION,
+    &day12::SOLUTION,
+    &day13::SOLUTION,
+    &day14::SOLUTION,
+    &day15::SOLUTION,
+    &day16::SOLUTION,
+];
+
+pub fn get(day: u32) -> Option<&'static Solution> {
+    SOLUTIONS.iter().copied().find(|s| s.day == day)
+}
+
+// Body of the day binaries: `solve` prints the answers for the input file given as the
+// only argument, or for the day's own input. Unreadable and malformed inputs, as well as
+// panicking solvers, end with an error logged on stderr and exit code 1 instead of a panic.
+pub fn main(solution: &Solution, solve: impl FnOnce(&str)) -> ExitCode {
+    let name = format!("day{}", solution.day);
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let path = match args.as_slice() {
+        [] => solution.input_path,
+        [path] if !path.starts_with('-') => path,
+        _ => {
+            eprintln!("usage: {} [INPUT]", name);
+            return ExitCode::from(2);
+        }
+    };
+
+    let input = match vault::read(path) {
+        Ok(input) => input,
+        Err(e) => {
+            log::error!("{}: {}", name, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Err(errors) = (solution.check_input)(&input) {
+        let lines: String = errors
+            .iter()
+            .map(|error| format!("\n  {}", error))
+            .collect();
+        log::error!(
+            "{}: {} has {} malformed line(s){}",
+            name,
+            path,
+            errors.len(),
+            lines
+        );
+        return ExitCode::FAILURE;
+    }
+
+    fuzz::silence_panics();
+    match fuzz::catch(|| solve(&input)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            log::error!("{}: solver panicked: {}", name, message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault;
+
+    // Every implementation must agree with the accepted answers
+    fn check_variants(solution: &Solution) {
+        let input = vault::read(solution.input_path).unwrap();
+        let answers = solution.answers().unwrap();
+        let params = Params::defaults(solution.params);
+
+        for variant in solution.variants {
+            for (part, answer) in (1..).zip(&answers) {
+                if let Some(solver) = variant.solver(part) {
+                    assert_eq!(
+                        &solver(&input, &params, &Progress::none()),
+                        answer,
+                        "day {} part {} [{}]",
+                        solution.day,
+                        part,
+                        variant.name
+                    );
+                }
+            }
+        }
+    }
+
+    // Parameters lowered for fuzzing make the runs with the defaults slow too
+    fn is_slow(solution: &Solution) -> bool {
+        solution.params.iter().any(|param| param.fuzz.is_some())
+    }
+
+    #[test]
+    fn solution_variants() {
+        assert_eq!(day10::SOLUTION.variant_names(), ["default", "dp"]);
+        assert_eq!(
+            day10::SOLUTION.variant("x").err(),
+            Some("day 10 has no implementation `x`, expected one of: default, dp".to_string())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+    fn solution_variants_input() {
+        for solution in SOLUTIONS.iter().filter(|solution| !is_slow(solution)) {
+            check_variants(solution);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "slow-tests"), ignore = "slow in debug builds")]
+    fn solution_variants_slow() {
+        for solution in SOLUTIONS.iter().filter(|solution| is_slow(solution)) {
+            check_variants(solution);
+        }
+    }
+}
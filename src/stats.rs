@@ -0,0 +1,220 @@
+//! Operation counters and histograms for profiling the solvers.
+//!
+//! Solvers use [`count!`] and [`record!`], which expand to nothing unless the `stats`
+//! feature is enabled. Values are only collected while running under [`capture`].
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::json::{Json, ToJson};
+
+#[cfg(feature = "stats")]
+use std::cell::RefCell;
+
+pub const ENABLED: bool = cfg!(feature = "stats");
+
+#[cfg(feature = "stats")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __stats_count {
+    ($name:expr) => {
+        $crate::stats::add($name, 1)
+    };
+    ($name:expr, $n:expr) => {
+        $crate::stats::add($name, $n as u64)
+    };
+}
+
+#[cfg(not(feature = "stats"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __stats_count {
+    ($($args:tt)*) => {};
+}
+
+#[cfg(feature = "stats")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __stats_record {
+    ($name:expr, $value:expr) => {
+        $crate::stats::record($name, $value as u64)
+    };
+}
+
+#[cfg(not(feature = "stats"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __stats_record {
+    ($($args:tt)*) => {};
+}
+
+// Increments a named counter, by one or by the given amount
+pub use __stats_count as count;
+// Adds a value to a named histogram
+pub use __stats_record as record;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Histogram {
+    pub count: u64,
+    pub sum: u64,
+    pub min: u64,
+    pub max: u64,
+    // Bucket `i` holds the values needing `i` bits, i.e. [2^(i-1), 2^i)
+    pub buckets: Vec<u64>,
+}
+
+impl Histogram {
+    pub fn add(&mut self, value: u64) {
+        if self.count == 0 || value < self.min {
+            self.min = value;
+        }
+        self.max = self.max.max(value);
+        self.count += 1;
+        self.sum += value;
+
+        let bucket = (u64::BITS - value.leading_zeros()) as usize;
+        if self.buckets.len() <= bucket {
+            self.buckets.resize(bucket + 1, 0);
+        }
+        self.buckets[bucket] += 1;
+    }
+}
+
+impl fmt::Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "n={} min={} max={} mean={:.1}",
+            self.count,
+            self.min,
+            self.max,
+            self.sum as f64 / self.count.max(1) as f64
+        )?;
+
+        let buckets: Vec<_> = self
+            .buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, &n)| n > 0)
+            .map(|(bits, n)| match bits {
+                0 => format!("0: {}", n),
+                1 => format!("1: {}", n),
+                _ => format!("{}-{}: {}", 1u64 << (bits - 1), (1u64 << bits) - 1, n),
+            })
+            .collect();
+
+        write!(f, " [{}]", buckets.join(", "))
+    }
+}
+
+impl ToJson for Histogram {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("count", self.count.to_json()),
+            ("sum", self.sum.to_json()),
+            ("min", self.min.to_json()),
+            ("max", self.max.to_json()),
+            ("buckets", self.buckets.to_json()),
+        ])
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub counters: BTreeMap<&'static str, u64>,
+    pub histograms: BTreeMap<&'static str, Histogram>,
+}
+
+impl Stats {
+    pub fn is_empty(&self) -> bool {
+        self.counters.is_empty() && self.histograms.is_empty()
+    }
+}
+
+impl ToJson for Stats {
+    fn to_json(&self) -> Json {
+        Json::object([
+            (
+                "counters",
+                Json::object(self.counters.iter().map(|(&k, v)| (k, v.to_json()))),
+            ),
+            (
+                "histograms",
+                Json::object(self.histograms.iter().map(|(&k, v)| (k, v.to_json()))),
+            ),
+        ])
+    }
+}
+
+#[cfg(feature = "stats")]
+thread_local! {
+    static STATS: RefCell<Option<Stats>> = const { RefCell::new(None) };
+}
+
+#[cfg(feature = "stats")]
+pub fn add(name: &'static str, n: u64) {
+    STATS.with(|stats| {
+        if let Some(stats) = stats.borrow_mut().as_mut() {
+            *stats.counters.entry(name).or_insert(0) += n;
+        }
+    });
+}
+
+#[cfg(feature = "stats")]
+pub fn record(name: &'static str, value: u64) {
+    STATS.with(|stats| {
+        if let Some(stats) = stats.borrow_mut().as_mut() {
+            stats.histograms.entry(name).or_default().add(value);
+        }
+    });
+}
+
+#[cfg(feature = "stats")]
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Stats) {
+    let previous = STATS.with(|stats| stats.replace(Some(Stats::default())));
+    let result = f();
+    let stats = STATS
+        .with(|stats| stats.replace(previous))
+        .unwrap_or_default();
+
+    (result, stats)
+}
+
+#[cfg(not(feature = "stats"))]
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Stats) {
+    (f(), Stats::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_histogram() {
+        let mut histogram = Histogram::default();
+        for value in [0, 1, 2, 3, 9] {
+            histogram.add(value);
+        }
+
+        assert_eq!((histogram.min, histogram.max, histogram.sum), (0, 9, 15));
+        assert_eq!(
+            histogram.to_string(),
+            "n=5 min=0 max=9 mean=3.0 [0: 1, 1: 1, 2-3: 2, 8-15: 1]"
+        );
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn stats_capture() {
+        count!("ignored");
+
+        let (_, stats) = capture(|| {
+            count!("calls");
+            count!("calls", 2);
+            record!("sizes", 4);
+        });
+
+        assert_eq!(stats.counters.get("calls"), Some(&3));
+        assert_eq!(stats.histograms["sizes"].count, 1);
+    }
+}
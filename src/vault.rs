@@ -0,0 +1,530 @@
+//! Encrypted storage of the puzzle inputs.
+//!
+//! An input may be committed as `input.txt.enc` instead of `input.txt`, encrypted with the
+//! passphrase in [`KEY_VAR`]. [`read`] falls back on the encrypted file when the plain one
+//! is missing, so the solvers, their tests and the runner never see the difference.
+//!
+//! The keys are derived from the passphrase with PBKDF2-HMAC-SHA256, salted per blob, the
+//! salt and the iteration count being stored in its header. The cipher is ChaCha20 followed
+//! by HMAC-SHA256 over the whole blob, encrypt-then-MAC.
+//!
+//! The inputs of this repository are still committed in plain text: encrypting them takes
+//! the passphrase of their owner, with `aoc inputs encrypt --all --remove`, and rewriting
+//! the history that holds them.
+
+use std::collections::hash_map::RandomState;
+use std::env;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+pub const EXTENSION: &str = "enc";
+// Of PBKDF2, each one taking two SHA-256 blocks
+pub const ITERATIONS: u32 = 200_000;
+// The count is read before the blob is authenticated, a corrupted one must not hang
+const MAX_ITERATIONS: u32 = 10 * ITERATIONS;
+
+const MAGIC: &[u8] = b"aoc-vault-2\n";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 32;
+// Salt then iteration count
+const HEADER_LEN: usize = MAGIC.len() + SALT_LEN + 4;
+
+const SHA256_IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+fn sha256_compress(h: &mut [u32; 8], block: &[u8]) {
+    let mut w = [0u32; 64];
+    for (i, word) in block.chunks(4).enumerate() {
+        w[i] = u32::from_be_bytes(word.try_into().unwrap());
+    }
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = *h;
+    for i in 0..64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = hh
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(SHA256_K[i])
+            .wrapping_add(w[i]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+
+        hh = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+
+    for (h, v) in h.iter_mut().zip([a, b, c, d, e, f, g, hh]) {
+        *h = h.wrapping_add(v);
+    }
+}
+
+fn digest(h: [u32; 8]) -> [u8; 32] {
+    let mut digest = [0; 32];
+    for (out, word) in digest.chunks_mut(4).zip(h) {
+        out.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    // The message, a 1 bit, zeros up to 56 bytes modulo 64, then its length in bits
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&(data.len() as u64 * 8).to_be_bytes());
+
+    let mut h = SHA256_IV;
+    for block in message.chunks(64) {
+        sha256_compress(&mut h, block);
+    }
+    digest(h)
+}
+
+// The key padded to a block and xored with `byte`
+fn hmac_pad(key: &[u8], byte: u8) -> [u8; 64] {
+    let mut block = [0u8; 64];
+    if key.len() > 64 {
+        block[..32].copy_from_slice(&sha256(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    block.map(|b| b ^ byte)
+}
+
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut inner = hmac_pad(key, 0x36).to_vec();
+    inner.extend_from_slice(data);
+    let mut outer = hmac_pad(key, 0x5c).to_vec();
+    outer.extend_from_slice(&sha256(&inner));
+    sha256(&outer)
+}
+
+// PBKDF2-HMAC-SHA256 for a 32-byte key. The hashes of the padded key blocks are the same
+// for every iteration, so each one only hashes the 32-byte block of the previous one.
+pub fn pbkdf2_sha256(password: &[u8], salt: &[u8], iterations: u32) -> [u8; 32] {
+    let state = |byte| {
+        let mut h = SHA256_IV;
+        sha256_compress(&mut h, &hmac_pad(password, byte));
+        h
+    };
+    let (inner, outer) = (state(0x36), state(0x5c));
+
+    // A 32-byte message after the key block: 96 bytes in all
+    let mut block = [0u8; 64];
+    block[32] = 0x80;
+    block[56..].copy_from_slice(&(96u64 * 8).to_be_bytes());
+
+    let mut message = salt.to_vec();
+    message.extend_from_slice(&1u32.to_be_bytes());
+    let mut u = hmac_sha256(password, &message);
+    let mut result = u;
+
+    for _ in 1..iterations {
+        block[..32].copy_from_slice(&u);
+        let mut h = inner;
+        sha256_compress(&mut h, &block);
+        block[..32].copy_from_slice(&digest(h));
+        let mut h = outer;
+        sha256_compress(&mut h, &block);
+        u = digest(h);
+
+        for (r, b) in result.iter_mut().zip(u) {
+            *r ^= b;
+        }
+    }
+
+    result
+}
+
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+fn chacha20_block(key: &[u8; 32], counter: u32, nonce: &[u8; NONCE_LEN]) -> [u8; 64] {
+    let word = |bytes: &[u8]| u32::from_le_bytes(bytes.try_into().unwrap());
+
+    // "expand 32-byte k"
+    let mut initial = [0u32; 16];
+    initial[..4].copy_from_slice(&[0x61707865, 0x3320646e, 0x79622d32, 0x6b206574]);
+    for i in 0..8 {
+        initial[4 + i] = word(&key[4 * i..4 * i + 4]);
+    }
+    initial[12] = counter;
+    for i in 0..3 {
+        initial[13 + i] = word(&nonce[4 * i..4 * i + 4]);
+    }
+
+    let mut state = initial;
+    for _ in 0..10 {
+        quarter_round(&mut state, 0, 4, 8, 12);
+        quarter_round(&mut state, 1, 5, 9, 13);
+        quarter_round(&mut state, 2, 6, 10, 14);
+        quarter_round(&mut state, 3, 7, 11, 15);
+        quarter_round(&mut state, 0, 5, 10, 15);
+        quarter_round(&mut state, 1, 6, 11, 12);
+        quarter_round(&mut state, 2, 7, 8, 13);
+        quarter_round(&mut state, 3, 4, 9, 14);
+    }
+
+    let mut block = [0; 64];
+    for (i, out) in block.chunks_mut(4).enumerate() {
+        out.copy_from_slice(&state[i].wrapping_add(initial[i]).to_le_bytes());
+    }
+    block
+}
+
+// Encryption and decryption alike, the counter of the first block being `counter`
+pub fn chacha20(key: &[u8; 32], counter: u32, nonce: &[u8; NONCE_LEN], data: &mut [u8]) {
+    for (idx, chunk) in data.chunks_mut(64).enumerate() {
+        let block = chacha20_block(key, counter.wrapping_add(idx as u32), nonce);
+        for (byte, k) in chunk.iter_mut().zip(block) {
+            *byte ^= k;
+        }
+    }
+}
+
+// The passphrase, the keys of each blob being derived from it and the salt of the blob
+pub struct Key {
+    passphrase: String,
+}
+
+// One per use
+struct Subkeys {
+    encryption: [u8; 32],
+    authentication: [u8; 32],
+    nonce: [u8; 32],
+}
+
+impl Key {
+    pub fn new(passphrase: &str) -> Self {
+        Self {
+            passphrase: passphrase.to_string(),
+        }
+    }
+
+    pub fn from_env() -> Result<Self, String> {
+        match env::var(KEY_VAR) {
+            Ok(passphrase) if !passphrase.is_empty() => Ok(Self::new(&passphrase)),
+            _ => Err(format!("{} is not set", KEY_VAR)),
+        }
+    }
+
+    fn derive(&self, salt: &[u8], iterations: u32) -> Subkeys {
+        let master = pbkdf2_sha256(self.passphrase.as_bytes(), salt, iterations);
+
+        Subkeys {
+            encryption: hmac_sha256(&master, b"encryption"),
+            authentication: hmac_sha256(&master, b"authentication"),
+            nonce: hmac_sha256(&master, b"nonce"),
+        }
+    }
+}
+
+// Only needs to differ from one blob to the next, std seeds its hashers from the OS
+fn random_salt() -> [u8; SALT_LEN] {
+    let mut salt = [0; SALT_LEN];
+
+    for chunk in salt.chunks_mut(8) {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_nanos()),
+        );
+        chunk.copy_from_slice(&hasher.finish().to_le_bytes());
+    }
+
+    salt
+}
+
+pub fn encrypt(key: &Key, plaintext: &[u8]) -> Vec<u8> {
+    encrypt_with(key, plaintext, &random_salt(), ITERATIONS)
+}
+
+// MAGIC, salt, iterations, nonce, ciphertext, then the tag of all of them
+pub fn encrypt_with(
+    key: &Key,
+    plaintext: &[u8],
+    salt: &[u8; SALT_LEN],
+    iterations: u32,
+) -> Vec<u8> {
+    let keys = key.derive(salt, iterations);
+    let nonce: [u8; NONCE_LEN] = hmac_sha256(&keys.nonce, plaintext)[..NONCE_LEN]
+        .try_into()
+        .unwrap();
+
+    let mut blob = MAGIC.to_vec();
+    blob.extend_from_slice(salt);
+    blob.extend_from_slice(&iterations.to_be_bytes());
+    blob.extend_from_slice(&nonce);
+    let start = blob.len();
+    blob.extend_from_slice(plaintext);
+    chacha20(&keys.encryption, 1, &nonce, &mut blob[start..]);
+
+    let tag = hmac_sha256(&keys.authentication, &blob);
+    blob.extend_from_slice(&tag);
+    blob
+}
+
+pub fn decrypt(key: &Key, blob: &[u8]) -> Result<Vec<u8>, String> {
+    if !blob.starts_with(MAGIC) {
+        return Err("not an encrypted input".to_string());
+    }
+    if blob.len() < HEADER_LEN + NONCE_LEN + TAG_LEN {
+        return Err("truncated encrypted input".to_string());
+    }
+
+    let salt = &blob[MAGIC.len()..MAGIC.len() + SALT_LEN];
+    let iterations = u32::from_be_bytes(blob[HEADER_LEN - 4..HEADER_LEN].try_into().unwrap());
+    if !(1..=MAX_ITERATIONS).contains(&iterations) {
+        return Err(format!(
+            "invalid iteration count {}, corrupted file",
+            iterations
+        ));
+    }
+    let keys = key.derive(salt, iterations);
+
+    let (authenticated, tag) = blob.split_at(blob.len() - TAG_LEN);
+    let expected = hmac_sha256(&keys.authentication, authenticated);
+
+    // Compares every byte, the time taken telling nothing of where they differ
+    let difference = tag
+        .iter()
+        .zip(expected)
+        .fold(0, |acc, (a, b)| acc | (a ^ b));
+    if difference != 0 {
+        return Err("authentication failed, wrong key or corrupted file".to_string());
+    }
+
+    let (nonce, ciphertext) = authenticated[HEADER_LEN..].split_at(NONCE_LEN);
+    let mut plaintext = ciphertext.to_vec();
+    chacha20(
+        &keys.encryption,
+        1,
+        nonce.try_into().unwrap(),
+        &mut plaintext,
+    );
+    Ok(plaintext)
+}
+
+// `input.txt` is stored as `input.txt.enc`
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(EXTENSION);
+    PathBuf::from(name)
+}
+
+// The input at `path`, or its encrypted copy decrypted with the key of the environment
+pub fn read(path: impl AsRef<Path>) -> Result<String, String> {
+    read_with(path.as_ref(), Key::from_env)
+}
+
+fn read_with(path: &Path, key: impl FnOnce() -> Result<Key, String>) -> Result<String, String> {
+    let error = match fs::read_to_string(path) {
+        Ok(input) => return Ok(input),
+        Err(e) if e.kind() == ErrorKind::NotFound => e,
+        Err(e) => return Err(format!("cannot read {}: {}", path.display(), e)),
+    };
+
+    let encrypted = encrypted_path(path);
+    let blob = match fs::read(&encrypted) {
+        Ok(blob) => blob,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            return Err(format!("cannot read {}: {}", path.display(), error))
+        }
+        Err(e) => return Err(format!("cannot read {}: {}", encrypted.display(), e)),
+    };
+
+    let key = key().map_err(|e| format!("cannot decrypt {}: {}", encrypted.display(), e))?;
+    let plaintext = decrypt(&key, &blob)
+        .map_err(|e| format!("cannot decrypt {}: {}", encrypted.display(), e))?;
+
+    String::from_utf8(plaintext)
+        .map_err(|_| format!("cannot decrypt {}: not UTF-8", encrypted.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn vault_primitives() {
+        assert_eq!(
+            hex(&sha256(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex(&sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        // Two blocks once padded
+        assert_eq!(
+            hex(&sha256(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+
+        // RFC 4231, test case 2
+        assert_eq!(
+            hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+
+        // RFC 7914, section 11, and the usual longer run
+        assert_eq!(
+            hex(&pbkdf2_sha256(b"passwd", b"salt", 1)),
+            "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc"
+        );
+        assert_eq!(
+            hex(&pbkdf2_sha256(b"password", b"salt", 4096)),
+            "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a"
+        );
+
+        // RFC 8439, section 2.4.2
+        let key: [u8; 32] = (0..32).collect::<Vec<u8>>().try_into().unwrap();
+        let nonce = [0, 0, 0, 0, 0, 0, 0, 0x4a, 0, 0, 0, 0];
+        let mut data = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.".to_vec();
+        chacha20(&key, 1, &nonce, &mut data);
+        assert_eq!(
+            hex(&data[..32]),
+            "6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0b"
+        );
+        assert_eq!(hex(&data[data.len() - 10..]), "b40b8eedf2785e42874d");
+    }
+
+    // Few iterations, so that the tests run quickly in debug builds
+    const SALT: [u8; SALT_LEN] = [7; SALT_LEN];
+    const TEST_ITERATIONS: u32 = 10;
+
+    #[test]
+    fn vault_round_trip() {
+        let key = Key::new("correct horse battery staple");
+        let blob = encrypt_with(&key, b"1721\n979\n366", &SALT, TEST_ITERATIONS);
+
+        assert!(blob.starts_with(MAGIC));
+        assert_eq!(&blob[MAGIC.len()..HEADER_LEN - 4], SALT);
+        assert_eq!(
+            blob[HEADER_LEN - 4..HEADER_LEN],
+            TEST_ITERATIONS.to_be_bytes()
+        );
+        assert!(!blob.windows(4).any(|w| w == b"1721"));
+        assert_eq!(decrypt(&key, &blob).unwrap(), b"1721\n979\n366");
+
+        // Each blob has its own salt, hence its own keys
+        assert_ne!(random_salt(), random_salt());
+        let other = encrypt_with(&key, b"1721\n979\n366", &[8; SALT_LEN], TEST_ITERATIONS);
+        assert_ne!(other[HEADER_LEN..], blob[HEADER_LEN..]);
+        assert_eq!(decrypt(&key, &other).unwrap(), b"1721\n979\n366");
+
+        assert_eq!(
+            decrypt(&Key::new("wrong"), &blob),
+            Err("authentication failed, wrong key or corrupted file".to_string())
+        );
+        let mut tampered = blob.clone();
+        tampered[HEADER_LEN + NONCE_LEN] ^= 1;
+        assert!(decrypt(&key, &tampered).is_err());
+        // Fewer iterations derive other keys
+        let mut weakened = blob.clone();
+        weakened[HEADER_LEN - 1] -= 1;
+        assert!(decrypt(&key, &weakened).is_err());
+        // Refused before deriving any key
+        let mut endless = blob.clone();
+        endless[HEADER_LEN - 4..HEADER_LEN].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert_eq!(
+            decrypt(&key, &endless),
+            Err("invalid iteration count 4294967295, corrupted file".to_string())
+        );
+        assert_eq!(
+            decrypt(&key, &blob[..HEADER_LEN + 4]),
+            Err("truncated encrypted input".to_string())
+        );
+        assert_eq!(
+            decrypt(&key, b"1721"),
+            Err("not an encrypted input".to_string())
+        );
+
+        assert_eq!(
+            encrypted_path(Path::new("src/day1/input.txt")),
+            PathBuf::from("src/day1/input.txt.enc")
+        );
+    }
+
+    #[test]
+    fn vault_read() {
+        let dir = env::temp_dir().join(format!("aoc-vault-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("input.txt");
+        let key = || Ok(Key::new("key"));
+
+        let error = read_with(&path, key).unwrap_err();
+        assert!(error.starts_with("cannot read "), "{}", error);
+
+        fs::write(
+            encrypted_path(&path),
+            encrypt_with(&Key::new("key"), b"1-3 a: abcde", &SALT, TEST_ITERATIONS),
+        )
+        .unwrap();
+        assert_eq!(read_with(&path, key), Ok("1-3 a: abcde".to_string()));
+        let error = read_with(&path, || Err(format!("{} is not set", KEY_VAR))).unwrap_err();
+        assert!(
+            error.ends_with("input.txt.enc: AOC_INPUT_KEY is not set"),
+            "{}",
+            error
+        );
+
+        // The plain input wins over the encrypted one
+        fs::write(&path, "2-9 c: ccccccccc").unwrap();
+        assert_eq!(read_with(&path, key), Ok("2-9 c: ccccccccc".to_string()));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
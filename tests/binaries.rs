@@ -0,0 +1,176 @@
+// Runs the day binaries like a user would, checking what they print and how they exit
+use std::process::{Command, Output};
+
+use aoc2020::day13;
+
+// Each binary with the answers it prints for tests/fixtures/<name>.txt
+const FIXTURES: [(&str, &str, &str); 15] = [
+    (
+        env!("CARGO_BIN_EXE_day1"),
+        "day1",
+        "Part 1: 514579\nPart 2: 241861950\n",
+    ),
+    (env!("CARGO_BIN_EXE_day2"), "day2", "Part 1: 2\nPart 2: 1\n"),
+    (
+        env!("CARGO_BIN_EXE_day3"),
+        "day3",
+        "Part 1: 7\nPart 2: 336\n",
+    ),
+    (env!("CARGO_BIN_EXE_day4"), "day4", "Part 1: 2\nPart 2: 2\n"),
+    (
+        env!("CARGO_BIN_EXE_day5"),
+        "day5",
+        "Part 1: 360\nPart 2: 359\n",
+    ),
+    (
+        env!("CARGO_BIN_EXE_day6"),
+        "day6",
+        "Part 1: 11\nPart 2: 6\n",
+    ),
+    (
+        env!("CARGO_BIN_EXE_day7"),
+        "day7",
+        "Part 1: 0\nPart 2: 126\n",
+    ),
+    (env!("CARGO_BIN_EXE_day8"), "day8", "Part 1: 5\nPart 2: 8\n"),
+    (
+        env!("CARGO_BIN_EXE_day9"),
+        "day9",
+        "Part 1: 100\nPart 2: 25\n",
+    ),
+    (
+        env!("CARGO_BIN_EXE_day10"),
+        "day10",
+        "Part 1: 35\nPart 2: 8\n",
+    ),
+    (
+        env!("CARGO_BIN_EXE_day11"),
+        "day11",
+        "Part 1: 37\nPart 2: 26\n",
+    ),
+    (
+        env!("CARGO_BIN_EXE_day12"),
+        "day12",
+        "Part 1: 25\nPart 2: 286\n",
+    ),
+    (env!("CARGO_BIN_EXE_day13"), "day13", "Part 1: 295\n"),
+    (
+        env!("CARGO_BIN_EXE_day14"),
+        "day14",
+        "Part 1: 51\nPart 2: 208\n",
+    ),
+    (
+        env!("CARGO_BIN_EXE_day16"),
+        "day16",
+        "Part 1: 71\nPart 2: 1\n",
+    ),
+];
+
+// Their part 2 takes 30 million turns
+const SLOW_FIXTURES: [(&str, &str, &str); 1] = [(
+    env!("CARGO_BIN_EXE_day15"),
+    "day15",
+    "Part 1: 436\nPart 2: 175594\n",
+)];
+
+fn run(exe: &str, args: &[&str]) -> (Output, String, String) {
+    let output = Command::new(exe).args(args).output().unwrap();
+    let stdout = String::from_utf8(output.stdout.clone()).unwrap();
+    let stderr = String::from_utf8(output.stderr.clone()).unwrap();
+    (output, stdout, stderr)
+}
+
+fn check_fixtures(fixtures: &[(&str, &str, &str)]) {
+    for (exe, name, expected) in fixtures {
+        let (output, stdout, stderr) = run(exe, &[&format!("tests/fixtures/{}.txt", name)]);
+
+        assert_eq!(stdout, *expected, "{}", name);
+        assert_eq!(stderr, "", "{}", name);
+        assert!(output.status.success(), "{}", name);
+    }
+}
+
+#[test]
+fn binaries_fixtures() {
+    check_fixtures(&FIXTURES);
+}
+
+#[test]
+#[cfg_attr(not(feature = "slow-tests"), ignore = "slow in debug builds")]
+fn binaries_slow_fixtures() {
+    check_fixtures(&SLOW_FIXTURES);
+}
+
+// Without an argument, the day's own input
+#[test]
+#[cfg_attr(not(feature = "input-tests"), ignore = "needs the puzzle input")]
+fn binaries_own_input() {
+    let answers = day13::SOLUTION.answers().unwrap();
+    let (output, stdout, _) = run(env!("CARGO_BIN_EXE_day13"), &[]);
+
+    assert_eq!(stdout, format!("Part 1: {}\n", answers[0]));
+    assert!(output.status.success());
+}
+
+#[test]
+fn binaries_missing_input() {
+    let (output, stdout, stderr) = run(env!("CARGO_BIN_EXE_day1"), &["tests/fixtures/missing.txt"]);
+
+    assert_eq!(stdout, "");
+    assert!(
+        stderr.starts_with("[ERROR solution] day1: cannot read tests/fixtures/missing.txt: "),
+        "{}",
+        stderr
+    );
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn binaries_malformed_input() {
+    let (output, stdout, stderr) = run(
+        env!("CARGO_BIN_EXE_day1"),
+        &["tests/fixtures/day1-malformed.txt"],
+    );
+
+    assert_eq!(stdout, "");
+    assert_eq!(
+        stderr,
+        "[ERROR solution] day1: tests/fixtures/day1-malformed.txt has 1 malformed line(s)\n  line 2: invalid number `abc` (\"abc\")\n"
+    );
+    assert_eq!(output.status.code(), Some(1));
+
+    // The parser indexes past the missing `contain` and the single-digit count, so the
+    // input is rejected as a whole rather than panicking halfway through the solver
+    let (output, stdout, stderr) = run(
+        env!("CARGO_BIN_EXE_day7"),
+        &["tests/fixtures/day7-malformed.txt"],
+    );
+
+    assert_eq!(stdout, "");
+    assert_eq!(
+        stderr,
+        "[ERROR solution] day7: tests/fixtures/day7-malformed.txt has 2 malformed line(s)
+  line 1: expected `<color> bags contain <content>.` (\"light red bags hold 1 bright white bag.\")
+  line 3: invalid bag count `three` (\"shiny gold bags contain three faded blue bags.\")
+"
+    );
+    assert!(!stderr.contains("panicked"), "{}", stderr);
+    assert_eq!(output.status.code(), Some(1));
+
+    let (output, _, stderr) = run(env!("CARGO_BIN_EXE_day1"), &["tests/fixtures/empty.txt"]);
+    assert!(
+        stderr.ends_with("line 1: empty input (\"\")\n"),
+        "{}",
+        stderr
+    );
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn binaries_usage() {
+    let (output, stdout, stderr) = run(env!("CARGO_BIN_EXE_day1"), &["a.txt", "b.txt"]);
+
+    assert_eq!(stdout, "");
+    assert_eq!(stderr, "usage: day1 [INPUT]\n");
+    assert_eq!(output.status.code(), Some(2));
+}